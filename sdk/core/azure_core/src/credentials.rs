@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, fmt::Debug, time::Duration};
-use typespec_client_core::date::OffsetDateTime;
+use typespec_client_core::date::{Clock, OffsetDateTime, SystemClock};
 
 pub static DEFAULT_SCOPE_SUFFIX: &str = "/.default";
 
@@ -86,7 +86,14 @@ impl AccessToken {
     ///
     /// If no duration is provided, then the default duration of 30 seconds is used.
     pub fn is_expired(&self, window: Option<Duration>) -> bool {
-        self.expires_on < OffsetDateTime::now_utc() + window.unwrap_or(Duration::from_secs(30))
+        self.is_expired_with_clock(&SystemClock, window)
+    }
+
+    /// Check if the token is expired within a given duration of the current time from the `clock`.
+    ///
+    /// If no duration is provided, then the default duration of 30 seconds is used.
+    pub fn is_expired_with_clock(&self, clock: &dyn Clock, window: Option<Duration>) -> bool {
+        self.expires_on < clock.now() + window.unwrap_or(Duration::from_secs(30))
     }
 }
 
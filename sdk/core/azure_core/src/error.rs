@@ -0,0 +1,198 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Errors, including the error details returned by Azure services.
+
+pub use typespec::error::*;
+pub use typespec_client_core::error::*;
+
+use crate::headers::ERROR_CODE;
+use serde::Deserialize;
+use std::iter;
+
+/// The details of an error returned by an Azure service.
+///
+/// Error details are parsed from JSON error responses as described in the
+/// [Azure REST API guidelines](https://github.com/microsoft/api-guidelines/blob/vNext/azure/Guidelines.md#handling-errors),
+/// and from XML error responses returned by Storage services, which only contain a code and message.
+///
+/// # Example
+///
+/// ```
+/// # use azure_core::error::AzureErrorDetail;
+/// # fn doc(err: &azure_core::Error) {
+/// if let Some(detail) = AzureErrorDetail::from_error(err) {
+///     println!("{:?}: {:?}", detail.code, detail.message);
+///     for inner in detail.inner_errors() {
+///         println!("caused by {:?}", inner.code);
+///     }
+/// }
+/// # }
+/// ```
+// cspell:ignore innererror
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct AzureErrorDetail {
+    /// A machine-readable error code defined by the service.
+    pub code: Option<String>,
+
+    /// A human-readable description of the error.
+    pub message: Option<String>,
+
+    /// The target of the error, e.g. the name of the property in error.
+    pub target: Option<String>,
+
+    /// Details about specific errors that led to this error.
+    #[serde(default)]
+    pub details: Vec<AzureErrorDetail>,
+
+    /// More specific information about the error.
+    #[serde(rename = "innererror")]
+    pub inner_error: Option<InnerError>,
+}
+
+/// More specific information about an [`AzureErrorDetail`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct InnerError {
+    /// A more specific error code than the containing error.
+    pub code: Option<String>,
+
+    /// More specific information about the error.
+    #[serde(rename = "innererror")]
+    pub inner_error: Option<Box<InnerError>>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: AzureErrorDetail,
+}
+
+#[cfg(feature = "xml")]
+#[derive(Deserialize)]
+#[serde(rename = "Error")]
+struct XmlErrorResponse {
+    #[serde(rename = "Code")]
+    code: Option<String>,
+    #[serde(rename = "Message")]
+    message: Option<String>,
+}
+
+impl AzureErrorDetail {
+    /// Parses the error details from a JSON or XML error response body.
+    ///
+    /// Returns `None` if the body does not contain an error code or message.
+    /// XML bodies are only parsed if the `xml` feature is enabled.
+    pub fn from_body(body: &[u8]) -> Option<Self> {
+        let detail = match body.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') => crate::json::from_json::<_, ErrorResponse>(body)
+                .map(|response| response.error)
+                .or_else(|_| crate::json::from_json(body))
+                .ok()?,
+            #[cfg(feature = "xml")]
+            Some(b'<') | Some(0xEF) => {
+                let response: XmlErrorResponse = crate::xml::read_xml(body).ok()?;
+                Self {
+                    code: response.code,
+                    message: response.message,
+                    ..Default::default()
+                }
+            }
+            _ => return None,
+        };
+        (detail.code.is_some() || detail.message.is_some()).then_some(detail)
+    }
+
+    /// Gets the error details of the [`HttpError`] in the chain of `error`, if any.
+    ///
+    /// See [`AzureErrorDetail::from_http_error`].
+    pub fn from_error(error: &Error) -> Option<Self> {
+        Self::from_http_error(HttpError::try_from(error)?)
+    }
+
+    /// Gets the error details of an [`HttpError`].
+    ///
+    /// If the body does not contain an error code, the code from the `x-ms-error-code` header is used.
+    pub fn from_http_error(error: &HttpError) -> Option<Self> {
+        let header_code = error.headers().get(ERROR_CODE.as_str());
+        let mut detail = match Self::from_body(error.body()) {
+            Some(detail) => detail,
+            None => Self {
+                code: Some(header_code?.clone()),
+                ..Default::default()
+            },
+        };
+        if detail.code.is_none() {
+            detail.code = header_code.cloned();
+        }
+        Some(detail)
+    }
+
+    /// Iterates over the chain of [`InnerError`]s, from the outermost to the innermost.
+    pub fn inner_errors(&self) -> impl Iterator<Item = &InnerError> {
+        iter::successors(self.inner_error.as_ref(), |inner| {
+            inner.inner_error.as_deref()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{headers::Headers, Response, StatusCode};
+
+    #[test]
+    fn parses_json_body() {
+        let detail = AzureErrorDetail::from_body(
+            br#"{"error":{"code":"BadArgument","message":"Invalid name.","target":"name",
+            "details":[{"code":"TooLong","message":"The name is too long.","target":"name"}],
+            "innererror":{"code":"NameTooLong","innererror":{"code":"MaxLengthExceeded"}}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(Some("BadArgument"), detail.code.as_deref());
+        assert_eq!(Some("name"), detail.target.as_deref());
+        assert_eq!(Some("TooLong"), detail.details[0].code.as_deref());
+        let inner: Vec<_> = detail
+            .inner_errors()
+            .map(|inner| inner.code.as_deref())
+            .collect();
+        assert_eq!(vec![Some("NameTooLong"), Some("MaxLengthExceeded")], inner);
+
+        let detail = AzureErrorDetail::from_body(br#"{"code":"NotFound","message":"Gone."}"#);
+        assert_eq!(Some("NotFound"), detail.unwrap().code.as_deref());
+        assert_eq!(None, AzureErrorDetail::from_body(b"{}"));
+        assert_eq!(None, AzureErrorDetail::from_body(b"not found"));
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn parses_xml_body() {
+        let detail = AzureErrorDetail::from_body(
+            b"\xEF\xBB\xBF<?xml version=\"1.0\" encoding=\"utf-8\"?><Error><Code>BlobNotFound</Code><Message>The specified blob does not exist.</Message></Error>",
+        )
+        .unwrap();
+        assert_eq!(Some("BlobNotFound"), detail.code.as_deref());
+        assert_eq!(
+            Some("The specified blob does not exist."),
+            detail.message.as_deref()
+        );
+    }
+
+    #[tokio::test]
+    async fn gets_detail_from_error() {
+        let mut headers = Headers::new();
+        headers.insert(ERROR_CODE, "ContainerAlreadyExists");
+        let response: Response<()> = Response::from_bytes(StatusCode::Conflict, headers, "");
+        let err = Error::new(
+            ErrorKind::http_response(StatusCode::Conflict, None),
+            HttpError::new(response).await,
+        )
+        .context("creating container");
+
+        assert!(err.is_conflict());
+        let detail = AzureErrorDetail::from_error(&err).unwrap();
+        assert_eq!(Some("ContainerAlreadyExists"), detail.code.as_deref());
+
+        let err = Error::message(ErrorKind::Io, "connection reset");
+        assert_eq!(None, AzureErrorDetail::from_error(&err));
+    }
+}
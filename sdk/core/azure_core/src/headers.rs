@@ -21,7 +21,6 @@ pub const BLOB_CONTENT_LENGTH: HeaderName = HeaderName::from_static("x-ms-blob-c
 pub const BLOB_PUBLIC_ACCESS: HeaderName = HeaderName::from_static("x-ms-blob-public-access");
 pub const BLOB_SEQUENCE_NUMBER: HeaderName = HeaderName::from_static("x-ms-blob-sequence-number");
 pub const BLOB_TYPE: HeaderName = HeaderName::from_static("x-ms-blob-type");
-pub const CLIENT_REQUEST_ID: HeaderName = HeaderName::from_static("x-ms-client-request-id");
 pub const CLIENT_VERSION: HeaderName = HeaderName::from_static("x-ms-client-version");
 pub const CONTENT_DISPOSITION: HeaderName =
     HeaderName::from_static("x-ms-blob-content-disposition");
@@ -57,7 +56,6 @@ pub const LEASE_TIME: HeaderName = HeaderName::from_static("x-ms-lease-time");
 pub const MAX_ITEM_COUNT: HeaderName = HeaderName::from_static("x-ms-max-item-count");
 pub const META_PREFIX: HeaderName = HeaderName::from_static("x-ms-meta-");
 pub const MS_DATE: HeaderName = HeaderName::from_static("x-ms-date");
-pub const MS_RANGE: HeaderName = HeaderName::from_static("x-ms-range");
 pub const NAMESPACE_ENABLED: HeaderName = HeaderName::from_static("x-ms-namespace-enabled");
 pub const PAGE_WRITE: HeaderName = HeaderName::from_static("x-ms-page-write");
 pub const PROPERTIES: HeaderName = HeaderName::from_static("x-ms-properties");
@@ -65,10 +63,11 @@ pub const PROPOSED_LEASE_ID: HeaderName = HeaderName::from_static("x-ms-proposed
 pub const RANGE_GET_CONTENT_CRC64: HeaderName =
     HeaderName::from_static("x-ms-range-get-content-crc64");
 pub const RANGE_GET_CONTENT_MD5: HeaderName = HeaderName::from_static("x-ms-range-get-content-md5");
-pub const REQUEST_ID: HeaderName = HeaderName::from_static("x-ms-request-id");
 pub const REQUEST_SERVER_ENCRYPTED: HeaderName =
     HeaderName::from_static("x-ms-request-server-encrypted");
 pub const REQUIRES_SYNC: HeaderName = HeaderName::from_static("x-ms-requires-sync");
+pub const RETURN_CLIENT_REQUEST_ID: HeaderName =
+    HeaderName::from_static("x-ms-return-client-request-id");
 pub const SERVER_ENCRYPTED: HeaderName = HeaderName::from_static("x-ms-server-encrypted");
 pub const SESSION_TOKEN: HeaderName = HeaderName::from_static("x-ms-session-token");
 pub const SKU_NAME: HeaderName = HeaderName::from_static("x-ms-sku-name");
@@ -44,24 +44,29 @@ pub use policies::*;
 
 // Re-export typespec types that are not specific to Azure.
 pub use typespec::{Error, Result};
-pub mod error {
-    pub use typespec::error::*;
-    pub use typespec_client_core::error::*;
-}
+pub mod error;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub use typespec_client_core::http::blocking;
+#[cfg(feature = "metrics")]
+pub use typespec_client_core::http::MetricsMeter;
 #[cfg(feature = "xml")]
 pub use typespec_client_core::xml;
 pub use typespec_client_core::{
-    base64, date,
+    async_runtime, base64, date,
     http::{
         headers::Header,
-        new_http_client,
+        new_http_client, new_http_client_with_options,
         response::{Model, PinnedStream, Response, ResponseBody},
-        AppendToUrlQuery, Body, Context, HttpClient, Method, Pager, Request, RequestContent,
+        AppendToUrlQuery, Body, CancellationToken, Context, Deadline, HttpClient, ItemPager, Meter,
+        Method, MetricLabels, OperationName, Page, Pager, PagerOptions, Request, RequestContent,
         StatusCode, Url,
     },
     json, parsing,
     sleep::{self, sleep},
-    stream::{BytesStream, SeekableStream},
+    stream::{
+        BandwidthLimiter, BytesStream, ProgressCallback, ProgressStream, SeekableStream,
+        ThrottledStream, TransferDirection, TransferProgress,
+    },
     Bytes, Uuid,
 };
 
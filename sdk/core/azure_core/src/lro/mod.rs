@@ -1,9 +1,12 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+mod poller;
+
 use crate::headers::Headers;
+pub use poller::*;
 use std::time::Duration;
-use typespec_client_core::date::OffsetDateTime;
+use typespec_client_core::date::SystemClock;
 
 /// Default retry time for long running operations if no retry-after header is present
 ///
@@ -14,7 +17,7 @@ const DEFAULT_RETRY_TIME: Duration = Duration::from_secs(30);
 /// Long Running Operation (LRO) status
 ///
 /// Ref: <https://learn.microsoft.com/en-us/azure/azure-resource-manager/management/async-operations#provisioningstate-values>
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LroStatus {
     InProgress,
     Succeeded,
@@ -25,23 +28,24 @@ pub enum LroStatus {
 
 impl From<&str> for LroStatus {
     fn from(s: &str) -> Self {
-        match s {
-            "InProgress" => LroStatus::InProgress,
-            "Succeeded" => LroStatus::Succeeded,
-            "Failed" => LroStatus::Failed,
+        // Services differ in the casing of statuses, so they are compared case-insensitively.
+        match s.to_ascii_lowercase().as_str() {
+            "inprogress" => LroStatus::InProgress,
+            "succeeded" => LroStatus::Succeeded,
+            "failed" => LroStatus::Failed,
             // While the specification indicates we should use `Canceled`, in
             // practice numerous services use `Cancelled`.  As such, we support
             // both.
             //
             // Ref: <https://github.com/Azure/azure-resource-manager-rpc/issues/144>
-            "Canceled" | "Cancelled" => LroStatus::Canceled,
+            "canceled" | "cancelled" => LroStatus::Canceled,
             _ => LroStatus::Other(s.to_owned()),
         }
     }
 }
 
 pub fn get_retry_after(headers: &Headers) -> Duration {
-    crate::get_retry_after(headers, OffsetDateTime::now_utc).unwrap_or(DEFAULT_RETRY_TIME)
+    crate::get_retry_after_with_clock(headers, &SystemClock).unwrap_or(DEFAULT_RETRY_TIME)
 }
 
 pub mod location {
@@ -0,0 +1,645 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    base64,
+    error::{http_response_from_body, Error, ErrorKind, HttpError, ResultExt},
+    headers::{Headers, AZURE_ASYNCOPERATION, LOCATION, OPERATION_LOCATION},
+    json::{from_json, to_json},
+    lro::{body_content, location, LroStatus, DEFAULT_RETRY_TIME},
+    Bytes, CancellationToken, Context, Deadline, Method, Pipeline, Request, Response, StatusCode,
+    Url,
+};
+use futures::{
+    future::{self, Either},
+    stream::{self, Stream},
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, marker::PhantomData, pin::pin, time::Duration};
+
+/// How a [`Poller`] gets the status of a long-running operation.
+///
+/// Ref: <https://github.com/microsoft/api-guidelines/blob/vNext/azure/ConsiderationsForServiceDesign.md#long-running-operations>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PollingStrategy {
+    /// Polls the status monitor in the `Operation-Location` header of the initial response.
+    OperationLocation,
+
+    /// Polls the status monitor in the `Azure-AsyncOperation` header of the initial response.
+    AzureAsyncOperation,
+
+    /// Polls the URL in the `Location` header of the initial response until it no longer returns `202 Accepted`.
+    Location,
+
+    /// Polls the resource created or updated by a `PUT` or `PATCH` request for its `provisioningState`.
+    Body,
+}
+
+/// The state of a [`Poller`] saved in a resume token.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PollerState {
+    strategy: PollingStrategy,
+    polling_url: String,
+    /// The URL to get the result from once the operation succeeds, if not the last polling response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    final_url: Option<String>,
+}
+
+/// Polls a long-running operation until it completes.
+///
+/// Create a `Poller` from the initial response of a long-running operation using [`Poller::from_response`].
+/// The polling strategy is chosen from the headers of the initial response: the `Operation-Location` header is preferred,
+/// then the `Azure-AsyncOperation` header, then the `Location` header; otherwise the `provisioningState` of the resource created or updated by a `PUT` or `PATCH`
+/// request is polled.
+/// Between polls, the `Poller` waits for the duration in any `retry-after` header, or its [frequency](Poller::with_frequency).
+///
+/// Save a `Poller` with [`Poller::resume_token`] and continue polling later, even in another process,
+/// with [`Poller::from_resume_token`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use azure_core::{lro::Poller, Context, Method, Pipeline, Request};
+/// # async fn doc(pipeline: Pipeline) -> azure_core::Result<()> {
+/// let mut request = Request::new("https://example.com/items/1".parse()?, Method::Put);
+/// let response = pipeline.send::<()>(&Context::new(), &mut request).await?;
+/// let poller: Poller<serde_json::Value> =
+///     Poller::from_response(pipeline, Context::new(), &request, response).await?;
+/// let item = poller.wait_until_done().await?.into_body().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Poller<T> {
+    pipeline: Pipeline,
+    context: Context<'static>,
+    state: PollerState,
+    status: LroStatus,
+    retry_after: Option<Duration>,
+    frequency: Duration,
+    last_response: Option<(StatusCode, Headers, Bytes)>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Poller<T> {
+    /// Creates a `Poller` from the initial `response` to the `request` that started a long-running operation.
+    ///
+    /// Polling requests are sent using the `pipeline` and `context`. If the `context` contains a [`CancellationToken`]
+    /// or [`Deadline`], waiting for the operation stops when it is cancelled or the deadline passes.
+    pub async fn from_response<U>(
+        pipeline: Pipeline,
+        context: Context<'_>,
+        request: &Request,
+        response: Response<U>,
+    ) -> crate::Result<Self> {
+        let (status_code, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+
+        let header_url = |strategy| match strategy {
+            PollingStrategy::OperationLocation => headers.get_optional_str(&OPERATION_LOCATION),
+            PollingStrategy::AzureAsyncOperation => headers.get_optional_str(&AZURE_ASYNCOPERATION),
+            PollingStrategy::Location => headers.get_optional_str(&LOCATION),
+            PollingStrategy::Body => None,
+        };
+        let (strategy, polling_url) = [
+            PollingStrategy::OperationLocation,
+            PollingStrategy::AzureAsyncOperation,
+            PollingStrategy::Location,
+        ]
+        .into_iter()
+        .find_map(|strategy| Some((strategy, header_url(strategy)?.to_string())))
+        .map_or_else(
+            || match request.method() {
+                // Only the resource created or updated by a PUT or PATCH can be polled for its status.
+                Method::Put | Method::Patch => {
+                    Ok((PollingStrategy::Body, request.url().to_string()))
+                }
+                method => Err(Error::with_message(ErrorKind::Other, || {
+                    format!("the response to a {method} request has no header to poll the long-running operation with")
+                })),
+            },
+            Ok,
+        )?;
+
+        let final_url = match strategy {
+            PollingStrategy::OperationLocation | PollingStrategy::AzureAsyncOperation => {
+                if matches!(request.method(), Method::Put | Method::Patch) {
+                    Some(request.url().to_string())
+                } else {
+                    headers.get_optional_string(&LOCATION)
+                }
+            }
+            PollingStrategy::Location | PollingStrategy::Body => None,
+        };
+
+        let status = match strategy {
+            PollingStrategy::Body => body_status(status_code, &body)?,
+            _ => LroStatus::InProgress,
+        };
+
+        let mut poller = Self::new(
+            pipeline,
+            context,
+            PollerState {
+                strategy,
+                polling_url,
+                final_url,
+            },
+        )?;
+        poller.status = status;
+        poller.retry_after = poller.get_retry_after(&headers);
+        poller.last_response = Some((status_code, headers, body));
+        Ok(poller)
+    }
+
+    /// Creates a `Poller` from a token returned by [`Poller::resume_token`].
+    ///
+    /// The operation is polled again before its status is known.
+    pub fn from_resume_token(
+        pipeline: Pipeline,
+        context: Context<'_>,
+        token: &str,
+    ) -> crate::Result<Self> {
+        let state: PollerState = from_json(base64::decode_url_safe(token)?)
+            .context(ErrorKind::DataConversion, "invalid resume token")?;
+        let mut poller = Self::new(pipeline, context, state)?;
+        poller.retry_after = Some(Duration::ZERO);
+        Ok(poller)
+    }
+
+    fn new(pipeline: Pipeline, context: Context<'_>, state: PollerState) -> crate::Result<Self> {
+        parse_url(&state.polling_url)?;
+        if let Some(final_url) = &state.final_url {
+            parse_url(final_url)?;
+        }
+
+        let mut context = context.into_owned();
+        if context.value::<CancellationToken>().is_none() {
+            context.insert(CancellationToken::new());
+        }
+        Ok(Self {
+            pipeline,
+            context,
+            state,
+            status: LroStatus::InProgress,
+            retry_after: None,
+            frequency: DEFAULT_RETRY_TIME,
+            last_response: None,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Sets how long to wait between polls when the service does not return a `retry-after` header.
+    ///
+    /// The default is 30 seconds.
+    pub fn with_frequency(mut self, frequency: Duration) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Gets the strategy used to poll the operation.
+    pub fn strategy(&self) -> PollingStrategy {
+        self.state.strategy
+    }
+
+    /// Gets the last known status of the operation.
+    pub fn status(&self) -> &LroStatus {
+        &self.status
+    }
+
+    /// Returns `true` if the operation succeeded, failed, or was canceled.
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.status,
+            LroStatus::Succeeded | LroStatus::Failed | LroStatus::Canceled
+        )
+    }
+
+    /// Gets a token to resume polling the operation with [`Poller::from_resume_token`].
+    ///
+    /// The token is an opaque, URL-safe string.
+    pub fn resume_token(&self) -> crate::Result<String> {
+        Ok(base64::encode_url_safe(to_json(&self.state)?))
+    }
+
+    /// Gets the [`CancellationToken`] that stops waiting for the operation.
+    ///
+    /// This is the token in the `Context` the `Poller` was created with, if any.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.context
+            .value::<CancellationToken>()
+            .cloned()
+            .expect("poller context has a cancellation token")
+    }
+
+    /// Stops waiting for the operation.
+    ///
+    /// Any pending or later wait returns an [`ErrorKind::Cancelled`] error. This does not cancel the operation in the service.
+    pub fn cancel(&self) {
+        self.cancellation_token().cancel();
+    }
+
+    /// Polls the status of the operation once, without waiting.
+    pub async fn poll(&mut self) -> crate::Result<LroStatus> {
+        let url = parse_url(&self.state.polling_url)?;
+        let mut request = Request::new(url, Method::Get);
+        let response = self
+            .pipeline
+            .send::<()>(&self.context, &mut request)
+            .await?;
+        let (status_code, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+
+        self.status = match self.state.strategy {
+            PollingStrategy::OperationLocation | PollingStrategy::AzureAsyncOperation => {
+                location::get_provisioning_state(&body).ok_or_else(|| {
+                    Error::message(
+                        ErrorKind::DataConversion,
+                        "status monitor response did not contain a status",
+                    )
+                })?
+            }
+            PollingStrategy::Location => match status_code {
+                StatusCode::Accepted => {
+                    if let Some(location) = headers.get_optional_string(&LOCATION) {
+                        self.state.polling_url = location;
+                    }
+                    LroStatus::InProgress
+                }
+                StatusCode::Ok | StatusCode::Created | StatusCode::NoContent => {
+                    LroStatus::Succeeded
+                }
+                status => {
+                    return Err(ErrorKind::HttpResponse {
+                        status,
+                        error_code: Some("invalid status found in LRO response".to_owned()),
+                    }
+                    .into_error())
+                }
+            },
+            PollingStrategy::Body => body_status(status_code, &body)?,
+        };
+        self.retry_after = self.get_retry_after(&headers);
+        self.last_response = Some((status_code, headers, body));
+        Ok(self.status.clone())
+    }
+
+    /// Returns a stream of the status of the operation after each poll.
+    ///
+    /// Each poll waits for any `retry-after` duration or the [frequency](Poller::with_frequency) first.
+    /// The stream ends after the operation is done or an error is returned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn statuses(&mut self) -> impl Stream<Item = crate::Result<LroStatus>> + Send + '_ {
+        self.status_stream()
+    }
+
+    /// Returns a stream of the status of the operation after each poll.
+    ///
+    /// Each poll waits for any `retry-after` duration or the [frequency](Poller::with_frequency) first.
+    /// The stream ends after the operation is done or an error is returned.
+    #[cfg(target_arch = "wasm32")]
+    pub fn statuses(&mut self) -> impl Stream<Item = crate::Result<LroStatus>> + '_ {
+        self.status_stream()
+    }
+
+    fn status_stream(&mut self) -> impl Stream<Item = crate::Result<LroStatus>> + '_ {
+        stream::unfold(Some(self), |poller| async move {
+            let poller = poller?;
+            if poller.is_done() {
+                return None;
+            }
+            let result = match poller.wait().await {
+                Ok(()) => poller.poll().await,
+                Err(err) => Err(err),
+            };
+            let next = result.is_ok().then_some(poller);
+            Some((result, next))
+        })
+    }
+
+    /// Polls the operation until it is done and returns its result.
+    ///
+    /// If the operation succeeded, the result is read from the resource or the last polling response.
+    /// Returns an error if waiting was [cancelled](Poller::cancel), or an [`ErrorKind::HttpResponse`] error
+    /// containing an [`HttpError`] for the last polling response if the operation failed or was canceled.
+    pub async fn wait_until_done(mut self) -> crate::Result<Response<T>> {
+        while !self.is_done() {
+            self.wait().await?;
+            self.poll().await?;
+        }
+
+        let message = match self.status {
+            LroStatus::Succeeded => None,
+            LroStatus::Failed => Some("the long-running operation failed"),
+            _ => Some("the long-running operation was canceled"),
+        };
+        if let Some(message) = message {
+            // Return the last polling response, which usually describes the error, for callers to inspect.
+            let (status, headers, body) = self
+                .last_response
+                .take()
+                .expect("operation is done after a response");
+            let kind = http_response_from_body(status, &body);
+            let http_error =
+                HttpError::new(Response::<()>::from_bytes(status, headers, body)).await;
+            return Err(Error::full(kind, http_error, message));
+        }
+
+        if let Some(final_url) = &self.state.final_url {
+            let mut request = Request::new(parse_url(final_url)?, Method::Get);
+            return self.pipeline.send(&self.context, &mut request).await;
+        }
+        let (status, headers, body) = self
+            .last_response
+            .take()
+            .expect("operation is done after a response");
+        Ok(Response::from_bytes(status, headers, body))
+    }
+
+    fn get_retry_after(&self, headers: &Headers) -> Option<Duration> {
+        crate::get_retry_after_with_clock(headers, self.pipeline.clock(&self.context).as_ref())
+    }
+
+    /// Waits until the next poll, or returns an error if waiting is cancelled or the deadline passes.
+    async fn wait(&mut self) -> crate::Result<()> {
+        let delay = self.retry_after.take().unwrap_or(self.frequency);
+        let clock = self.pipeline.clock(&self.context);
+        let (delay, deadline) = match self.context.value::<Deadline>().map(|deadline| {
+            deadline
+                .remaining_with_clock(clock.as_ref())
+                .unwrap_or_default()
+        }) {
+            Some(remaining) if remaining < delay => (remaining, true),
+            _ => (delay, false),
+        };
+
+        let runtime = self.pipeline.runtime(&self.context);
+        let token = self.cancellation_token();
+        if token.is_cancelled() {
+            return Err(cancelled_error());
+        }
+        let sleep = runtime.sleep(delay);
+        let cancelled = pin!(token.cancelled());
+        let result = future::select(sleep, cancelled).await;
+        match result {
+            Either::Left(_) if deadline => Err(Error::message(
+                ErrorKind::Cancelled,
+                "the deadline for the operation has passed",
+            )),
+            Either::Left(_) => Ok(()),
+            Either::Right(_) => Err(cancelled_error()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Poller<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poller")
+            .field("strategy", &self.state.strategy)
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+fn parse_url(url: &str) -> crate::Result<Url> {
+    url.parse()
+        .with_context(ErrorKind::DataConversion, || format!("invalid URL: {url}"))
+}
+
+fn body_status(status_code: StatusCode, body: &Bytes) -> crate::Result<LroStatus> {
+    let body: serde_json::Value = if body.is_empty() {
+        serde_json::Value::Null
+    } else {
+        from_json(body)?
+    };
+    body_content::get_provisioning_state(status_code, &body)
+}
+
+fn cancelled_error() -> Error {
+    Error::message(ErrorKind::Cancelled, "the operation was cancelled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientOptions, Policy, PolicyResult, TransportOptions};
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+    };
+
+    /// Returns queued responses and records the URLs requested.
+    #[derive(Debug, Default)]
+    struct Transport {
+        responses: Mutex<VecDeque<(StatusCode, Headers, &'static str)>>,
+        urls: Mutex<Vec<String>>,
+    }
+
+    impl Transport {
+        fn new(responses: impl IntoIterator<Item = (StatusCode, Headers, &'static str)>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into_iter().collect()),
+                urls: Mutex::default(),
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.urls.lock().unwrap().push(request.url().to_string());
+            let (status, headers, body) = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("unexpected request");
+            Ok(Response::from_bytes(status, headers, body))
+        }
+    }
+
+    fn pipeline(transport: Arc<Transport>) -> Pipeline {
+        let options = ClientOptions {
+            transport: Some(TransportOptions::new_custom_policy(transport)),
+            ..Default::default()
+        };
+        Pipeline::new(None, None, options, Vec::new(), Vec::new())
+    }
+
+    fn headers(name: crate::headers::HeaderName, value: &'static str) -> Headers {
+        let mut headers = Headers::new();
+        headers.insert(name, value);
+        headers
+    }
+
+    #[tokio::test]
+    async fn polls_operation_location() {
+        let transport = Arc::new(Transport::new([
+            (StatusCode::Ok, Headers::new(), r#"{"status":"Running"}"#),
+            // Statuses are compared case-insensitively.
+            (StatusCode::Ok, Headers::new(), r#"{"status":"succeeded"}"#),
+            (StatusCode::Ok, Headers::new(), r#"{"name":"item"}"#),
+        ]));
+        let request = Request::new("https://example.com/items/1".parse().unwrap(), Method::Put);
+        let initial = Response::<()>::from_bytes(
+            StatusCode::Created,
+            headers(OPERATION_LOCATION, "https://example.com/operations/1"),
+            "",
+        );
+
+        let mut poller: Poller<serde_json::Value> = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            initial,
+        )
+        .await
+        .unwrap();
+        poller = poller.with_frequency(Duration::ZERO);
+        assert_eq!(PollingStrategy::OperationLocation, poller.strategy());
+
+        let statuses: Vec<LroStatus> = futures::StreamExt::collect::<Vec<_>>(poller.statuses())
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            vec![
+                LroStatus::Other("Running".to_string()),
+                LroStatus::Succeeded
+            ],
+            statuses
+        );
+
+        let item = poller.wait_until_done().await.unwrap().into_body().await;
+        assert_eq!(serde_json::json!({"name": "item"}), item.unwrap());
+        assert_eq!(
+            vec![
+                "https://example.com/operations/1",
+                "https://example.com/operations/1",
+                "https://example.com/items/1",
+            ],
+            *transport.urls.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn resumes_location() {
+        let transport = Arc::new(Transport::new([
+            (
+                StatusCode::Accepted,
+                headers(LOCATION, "https://example.com/operations/2"),
+                "",
+            ),
+            (StatusCode::Ok, Headers::new(), r#"{"name":"item"}"#),
+        ]));
+        let request = Request::new("https://example.com/items".parse().unwrap(), Method::Post);
+        let initial = Response::<()>::from_bytes(
+            StatusCode::Accepted,
+            headers(LOCATION, "https://example.com/operations/1"),
+            "",
+        );
+        let poller: Poller<serde_json::Value> = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            initial,
+        )
+        .await
+        .unwrap();
+        let token = poller.resume_token().unwrap();
+
+        let poller: Poller<serde_json::Value> =
+            Poller::from_resume_token(pipeline(transport.clone()), Context::new(), &token)
+                .unwrap()
+                .with_frequency(Duration::ZERO);
+        assert_eq!(PollingStrategy::Location, poller.strategy());
+        let item = poller.wait_until_done().await.unwrap().into_body().await;
+        assert_eq!(serde_json::json!({"name": "item"}), item.unwrap());
+        assert_eq!(
+            vec![
+                "https://example.com/operations/1",
+                "https://example.com/operations/2",
+            ],
+            *transport.urls.lock().unwrap()
+        );
+
+        let err = Poller::<()>::from_resume_token(pipeline(transport), Context::new(), "invalid")
+            .unwrap_err();
+        assert_eq!(ErrorKind::DataConversion, *err.kind());
+    }
+
+    #[tokio::test]
+    async fn cancels_waiting() {
+        let transport = Arc::new(Transport::default());
+        let request = Request::new("https://example.com/items/1".parse().unwrap(), Method::Put);
+        let initial = Response::<()>::from_bytes(
+            StatusCode::Created,
+            headers(AZURE_ASYNCOPERATION, "https://example.com/operations/1"),
+            "",
+        );
+        let poller: Poller<()> = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            initial,
+        )
+        .await
+        .unwrap();
+        assert_eq!(PollingStrategy::AzureAsyncOperation, poller.strategy());
+
+        poller.cancel();
+        let err = poller.wait_until_done().await.unwrap_err();
+        assert_eq!(ErrorKind::Cancelled, *err.kind());
+        assert!(transport.urls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn returns_failed_response() {
+        let transport = Arc::new(Transport::new([(
+            StatusCode::Ok,
+            Headers::new(),
+            r#"{"status":"Failed","error":{"code":"QuotaExceeded","message":"Quota exceeded."}}"#,
+        )]));
+        let request = Request::new("https://example.com/items".parse().unwrap(), Method::Post);
+        let initial = Response::<()>::from_bytes(
+            StatusCode::Accepted,
+            headers(OPERATION_LOCATION, "https://example.com/operations/1"),
+            "",
+        );
+        let poller: Poller<()> = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            initial,
+        )
+        .await
+        .unwrap()
+        .with_frequency(Duration::ZERO);
+
+        let err = poller.wait_until_done().await.unwrap_err();
+        assert_eq!(
+            ErrorKind::http_response(StatusCode::Ok, Some("QuotaExceeded".to_string())),
+            *err.kind()
+        );
+        let http_error = HttpError::try_from(&err).unwrap();
+        assert!(std::str::from_utf8(http_error.body())
+            .unwrap()
+            .contains("Quota exceeded."));
+
+        // A POST cannot be polled without a polling header.
+        let initial = Response::<()>::from_bytes(StatusCode::Accepted, Headers::new(), "");
+        let err =
+            Poller::<()>::from_response(pipeline(transport), Context::new(), &request, initial)
+                .await
+                .unwrap_err();
+        assert_eq!(ErrorKind::Other, *err.kind());
+    }
+}
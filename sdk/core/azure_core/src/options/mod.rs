@@ -4,7 +4,12 @@
 mod telemetry;
 pub use telemetry::*;
 
+#[cfg(feature = "gzip")]
+pub use typespec_client_core::http::{CompressionEncoding, CompressionOptions};
+
 pub use typespec_client_core::http::{
-    builders, ClientMethodOptions, ClientOptions, ExponentialRetryOptions, FixedRetryOptions,
-    RetryOptions, TransportOptions,
+    builders, BandwidthOptions, CircuitBreakerOptions, ClientCertificate, ClientMethodOptions,
+    ClientOptions, ExponentialRetryOptions, FixedRetryOptions, HttpClientOptions, Jitter,
+    LoggingOptions, MetricsOptions, ProxyOptions, RateLimitOptions, ResponseCacheOptions,
+    RetryBudget, RetryOptions, TransportOptions,
 };
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::{TelemetryOptions, TelemetryPolicy};
+use crate::{ClientRequestIdPolicy, TelemetryOptions, TelemetryPolicy};
 use std::{ops::Deref, sync::Arc};
 use typespec_client_core::http::{self, policies::Policy};
 
@@ -12,7 +12,7 @@ use typespec_client_core::http::{self, policies::Policy};
 /// 1. Client library-specified per-call policies are executed. Per-call policies can fail and bail out of the pipeline
 ///    immediately.
 /// 2. User-specified per-call policies are executed.
-/// 3. Telemetry policy.
+/// 3. Telemetry and client request ID policies.
 /// 4. Retry policy. It allows to re-execute the following policies.
 /// 5. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
 ///    in case of retries.
@@ -36,13 +36,18 @@ impl Pipeline {
     ///
     /// Crates can simply pass `option_env!("CARGO_PKG_NAME")` and `option_env!("CARGO_PKG_VERSION")` for the
     /// `crate_name` and `crate_version` arguments respectively.
+    /// The `crate_name` labels metrics unless [`MetricsOptions::client_name`](crate::MetricsOptions::client_name) is set.
     pub fn new(
         crate_name: Option<&'static str>,
         crate_version: Option<&'static str>,
-        options: http::ClientOptions,
+        mut options: http::ClientOptions,
         per_call_policies: Vec<Arc<dyn Policy>>,
         per_retry_policies: Vec<Arc<dyn Policy>>,
     ) -> Self {
+        if let (Some(metrics), Some(crate_name)) = (options.metrics.as_mut(), crate_name) {
+            metrics.client_name.get_or_insert(crate_name.into());
+        }
+
         let mut per_call_policies = per_call_policies.clone();
 
         let telemetry_policy = TelemetryPolicy::new(
@@ -52,6 +57,7 @@ impl Pipeline {
             &TelemetryOptions::default(),
         );
         per_call_policies.insert(0, Arc::new(telemetry_policy));
+        per_call_policies.insert(1, Arc::new(ClientRequestIdPolicy::default()));
 
         Self(http::Pipeline::new(
             options,
@@ -3,6 +3,7 @@
 
 use crate::{
     credentials::{AccessToken, TokenCredential},
+    date::{Clock, SystemClock},
     error::{Error, ErrorKind},
     headers::AUTHORIZATION,
     policies::{Policy, PolicyResult},
@@ -58,10 +59,13 @@ impl Policy for BearerTokenCredentialPolicy {
         request: &mut Request,
         next: &[Arc<dyn Policy>],
     ) -> PolicyResult {
+        let clock: &dyn Clock = ctx
+            .value::<Arc<dyn Clock>>()
+            .map_or(&SystemClock, |clock| clock.as_ref());
         let access_token = self.access_token.read().await;
 
         if let Some(token) = &(*access_token) {
-            if token.is_expired(Some(DEFAULT_REFRESH_TIME)) {
+            if token.is_expired_with_clock(clock, Some(DEFAULT_REFRESH_TIME)) {
                 drop(access_token);
                 let mut access_token = self.access_token.write().await;
                 *access_token = Some(self.credential.get_token(&self.scopes()).await?);
@@ -83,3 +87,85 @@ impl Policy for BearerTokenCredentialPolicy {
         next[0].send(ctx, request, &next[1..]).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        credentials::Secret, date::ManualClock, headers::Headers, Method, Response, StatusCode,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug)]
+    struct MockCredential {
+        clock: ManualClock,
+        calls: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl TokenCredential for MockCredential {
+        async fn get_token(&self, _scopes: &[&str]) -> crate::Result<AccessToken> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(AccessToken::new(
+                Secret::new(format!("token-{calls}")),
+                self.clock.now() + Duration::from_secs(600),
+            ))
+        }
+
+        async fn clear_cache(&self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Responder;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl Policy for Responder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let mut headers = Headers::new();
+            let authorization = request.headers().get_str(&AUTHORIZATION)?.to_string();
+            headers.insert(AUTHORIZATION, authorization);
+            Ok(Response::from_bytes(StatusCode::Ok, headers, ""))
+        }
+    }
+
+    #[tokio::test]
+    async fn refreshes_token_by_context_clock() {
+        let clock = ManualClock::default();
+        let credential = Arc::new(MockCredential {
+            clock: clock.clone(),
+            calls: AtomicUsize::new(0),
+        });
+        let policy = BearerTokenCredentialPolicy::new(credential.clone(), ["scope"]);
+        let next: Vec<Arc<dyn Policy>> = vec![Arc::new(Responder)];
+        let ctx = Context::new().with_value::<Arc<dyn Clock>>(Arc::new(clock.clone()));
+
+        let mut authorizations = Vec::new();
+        for advance in [0, 300, 300] {
+            clock.advance(Duration::from_secs(advance));
+            let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+            let response = policy.send(&ctx, &mut request, &next).await.unwrap();
+            authorizations.push(
+                response
+                    .headers()
+                    .get_str(&AUTHORIZATION)
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+
+        // The token is refreshed once it is within 2 minutes of expiring.
+        assert_eq!(
+            vec!["Bearer token-1", "Bearer token-1", "Bearer token-2"],
+            authorizations
+        );
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::headers::{HeaderValue, CLIENT_REQUEST_ID, RETURN_CLIENT_REQUEST_ID};
+use crate::request_options::ClientRequestId;
+use crate::{Context, Request, Uuid};
+use std::sync::Arc;
+use typespec_client_core::http::policies::{Policy, PolicyResult};
+
+/// Sets the `x-ms-client-request-id` header to correlate a call with service logs.
+///
+/// The ID is set once per call so that it is the same for every retry. If the request already has
+/// an `x-ms-client-request-id` header, it is used; otherwise, a [`ClientRequestId`] in the [`Context`]
+/// is used, or a new [`Uuid`] is generated.
+#[derive(Clone, Debug, Default)]
+pub struct ClientRequestIdPolicy {}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for ClientRequestIdPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if request
+            .headers()
+            .get_optional_str(&CLIENT_REQUEST_ID)
+            .is_none()
+        {
+            match ctx.value::<ClientRequestId>() {
+                Some(client_request_id) => request.add_mandatory_header(client_request_id),
+                None => request.insert_header(CLIENT_REQUEST_ID, Uuid::new_v4().to_string()),
+            }
+        }
+        request.insert_header(RETURN_CLIENT_REQUEST_ID, HeaderValue::from_static("true"));
+
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        headers::Headers, ClientOptions, FixedRetryOptions, Method, Pipeline, Response,
+        RetryOptions, StatusCode, TransportOptions,
+    };
+    use std::{sync::Mutex, time::Duration};
+
+    #[derive(Debug, Default)]
+    struct Responder {
+        client_request_ids: Mutex<Vec<String>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Responder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let client_request_id = request.headers().get_str(&CLIENT_REQUEST_ID)?.to_string();
+            let mut client_request_ids = self.client_request_ids.lock().unwrap();
+            client_request_ids.push(client_request_id.clone());
+
+            let status = if client_request_ids.len() == 1 {
+                StatusCode::ServiceUnavailable
+            } else {
+                StatusCode::Ok
+            };
+            let mut headers = Headers::new();
+            headers.insert(CLIENT_REQUEST_ID, client_request_id);
+            Ok(Response::from_bytes(status, headers, ""))
+        }
+    }
+
+    fn pipeline(transport: Arc<Responder>) -> Pipeline {
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default().delay(Duration::from_millis(10)),
+            )),
+            transport: Some(TransportOptions::new_custom_policy(transport)),
+            ..Default::default()
+        };
+        Pipeline::new(None, None, options, Vec::new(), Vec::new())
+    }
+
+    #[tokio::test]
+    async fn client_request_id_is_stable_across_retries() {
+        let transport = Arc::new(Responder::default());
+        let pipeline = pipeline(transport.clone());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let response = pipeline
+            .send::<()>(&Context::new(), &mut request)
+            .await
+            .unwrap();
+
+        let client_request_ids = transport.client_request_ids.lock().unwrap();
+        assert_eq!(2, client_request_ids.len());
+        assert_eq!(client_request_ids[0], client_request_ids[1]);
+        client_request_ids[0]
+            .parse::<Uuid>()
+            .expect("client request ID is a UUID");
+        assert_eq!(
+            Some(client_request_ids[0].as_str()),
+            response.client_request_id()
+        );
+    }
+
+    #[tokio::test]
+    async fn client_request_id_from_context() {
+        let transport = Arc::new(Responder::default());
+        let pipeline = pipeline(transport.clone());
+
+        let ctx = Context::new().with_value(ClientRequestId::new("my-request"));
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline.send::<()>(&ctx, &mut request).await.unwrap();
+
+        assert_eq!(
+            vec!["my-request", "my-request"],
+            *transport.client_request_ids.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn client_request_id_from_request() {
+        let transport = Arc::new(Responder::default());
+        let pipeline = pipeline(transport.clone());
+
+        let ctx = Context::new().with_value(ClientRequestId::new("my-request"));
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        request.insert_header(CLIENT_REQUEST_ID, "my-header");
+        pipeline.send::<()>(&ctx, &mut request).await.unwrap();
+
+        assert_eq!(
+            vec!["my-header", "my-header"],
+            *transport.client_request_ids.lock().unwrap()
+        );
+    }
+}
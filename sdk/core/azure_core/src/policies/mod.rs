@@ -2,9 +2,11 @@
 // Licensed under the MIT License.
 
 mod bearer_token_policy;
+mod client_request_id;
 mod telemetry;
 
 pub use bearer_token_policy::BearerTokenCredentialPolicy;
+pub use client_request_id::ClientRequestIdPolicy;
 
 pub use telemetry::*;
 pub use typespec_client_core::http::policies::*;
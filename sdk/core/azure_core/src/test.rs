@@ -9,6 +9,8 @@
 use crate::error::{Error, ErrorKind};
 use std::{fmt, str::FromStr};
 
+pub use typespec_client_core::http::{ExpectedRequest, MockHttpClient, MockResponse};
+
 /// Whether to test client methods by playing back recordings, recording live sessions, or executing live sessions without recording.
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TestMode {
@@ -666,7 +666,7 @@ impl ContainerClient {
     /// ```
     ///
     /// See [`PartitionKey`](crate::PartitionKey) for more information on how to specify a partition key, and [`Query`] for more information on how to specify a query.
-    pub fn query_items<T: DeserializeOwned + Send>(
+    pub fn query_items<T: DeserializeOwned + Send + 'static>(
         &self,
         query: impl Into<Query>,
         partition_key: impl Into<QueryPartitionStrategy>,
@@ -683,6 +683,7 @@ impl ContainerClient {
             query.into(),
             base_request,
             self.items_link.clone(),
+            options.pager_options,
         )
     }
 }
@@ -139,6 +139,7 @@ impl CosmosClient {
             query.into(),
             base_request,
             self.databases_link.clone(),
+            options.pager_options,
         )
     }
 
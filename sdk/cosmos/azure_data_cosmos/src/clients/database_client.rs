@@ -119,6 +119,7 @@ impl DatabaseClient {
             query.into(),
             base_request,
             self.containers_link.clone(),
+            options.pager_options,
         )
     }
 
@@ -3,7 +3,7 @@
 
 //! Model types sent to and received from the Azure Cosmos DB API.
 
-use azure_core::{date::OffsetDateTime, Model};
+use azure_core::{date::OffsetDateTime, Model, Page};
 use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
 
 mod container_properties;
@@ -53,6 +53,15 @@ impl<T: DeserializeOwned> azure_core::Model for QueryResults<T> {
     }
 }
 
+impl<T> Page for QueryResults<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_items(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
 /// A page of results from [`CosmosClient::query_databases`](crate::CosmosClient::query_databases())
 #[non_exhaustive]
 #[derive(Clone, Default, Debug, Deserialize, Model)]
@@ -61,6 +70,15 @@ pub struct DatabaseQueryResults {
     pub databases: Vec<DatabaseProperties>,
 }
 
+impl Page for DatabaseQueryResults {
+    type Item = DatabaseProperties;
+    type IntoIter = std::vec::IntoIter<DatabaseProperties>;
+
+    fn into_items(self) -> Self::IntoIter {
+        self.databases.into_iter()
+    }
+}
+
 /// A page of results from [`DatabaseClient::query_containers`](crate::clients::DatabaseClient::query_containers())
 #[non_exhaustive]
 #[derive(Clone, Default, Debug, Deserialize, Model)]
@@ -69,6 +87,15 @@ pub struct ContainerQueryResults {
     pub containers: Vec<ContainerProperties>,
 }
 
+impl Page for ContainerQueryResults {
+    type Item = ContainerProperties;
+    type IntoIter = std::vec::IntoIter<ContainerProperties>;
+
+    fn into_items(self) -> Self::IntoIter {
+        self.containers.into_iter()
+    }
+}
+
 /// Common system properties returned for most Cosmos DB resources.
 #[non_exhaustive]
 #[derive(Clone, Default, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use azure_core::{ClientMethodOptions, ClientOptions};
+use azure_core::{ClientMethodOptions, ClientOptions, PagerOptions};
 
 use crate::models::ThroughputProperties;
 
@@ -58,18 +58,27 @@ pub struct ItemOptions<'a> {
 #[derive(Clone, Default)]
 pub struct QueryContainersOptions<'a> {
     pub method_options: ClientMethodOptions<'a>,
+
+    /// Options to resume the query from a continuation token and limit the number of results in each page.
+    pub pager_options: PagerOptions,
 }
 
 /// Options to be passed to [`CosmosClient::query_databases()`](crate::CosmosClient::query_databases())
 #[derive(Clone, Default)]
 pub struct QueryDatabasesOptions<'a> {
     pub method_options: ClientMethodOptions<'a>,
+
+    /// Options to resume the query from a continuation token and limit the number of results in each page.
+    pub pager_options: PagerOptions,
 }
 
 /// Options to be passed to [`ContainerClient::query_items()`](crate::clients::ContainerClient::query_items()).
 #[derive(Clone, Default)]
 pub struct QueryOptions<'a> {
     pub method_options: ClientMethodOptions<'a>,
+
+    /// Options to resume the query from a continuation token and limit the number of results in each page.
+    pub pager_options: PagerOptions,
 }
 
 /// Options to be passed to [`ContainerClient::read()`](crate::clients::ContainerClient::read()).
@@ -7,7 +7,7 @@ mod signature_target;
 use std::sync::Arc;
 
 pub use authorization_policy::AuthorizationPolicy;
-use azure_core::{ClientOptions, Context, Method, Model, Pager, Request, Response};
+use azure_core::{ClientOptions, Context, Method, Model, Pager, PagerOptions, Request, Response};
 use futures::StreamExt;
 use serde::{de::DeserializeOwned, Deserialize};
 use typespec_client_core::http::PagerResult;
@@ -64,14 +64,18 @@ impl CosmosPipeline {
         self.pipeline.send(&ctx, request).await
     }
 
-    pub fn send_query_request<T: DeserializeOwned>(
+    pub fn send_query_request<T: DeserializeOwned + 'static>(
         &self,
         ctx: Context<'_>,
         query: Query,
         mut base_request: Request,
         resource_link: ResourceLink,
+        pager_options: PagerOptions,
     ) -> azure_core::Result<Pager<T>> {
         base_request.insert_header(constants::QUERY, "True");
+        if let Some(max_page_size) = pager_options.max_page_size {
+            base_request.insert_header(constants::MAX_ITEM_COUNT, max_page_size.to_string());
+        }
         base_request.add_mandatory_header(&constants::QUERY_CONTENT_TYPE);
         base_request.set_json(&query)?;
 
@@ -79,26 +83,31 @@ impl CosmosPipeline {
         // First we clone the pipeline to pass it in to the closure
         let pipeline = self.pipeline.clone();
         let ctx = ctx.with_value(resource_link).into_owned();
-        Ok(Pager::from_callback(move |continuation| {
-            // Then we have to clone it again to pass it in to the async block.
-            // This is because Pageable can't borrow any data, it has to own it all.
-            // That's probably good, because it means a Pageable can outlive the client that produced it, but it requires some extra cloning.
-            let pipeline = pipeline.clone();
-            let mut req = base_request.clone();
-            let ctx = ctx.clone();
-            async move {
-                if let Some(continuation) = continuation {
-                    req.insert_header(constants::CONTINUATION, continuation);
+        let runtime = self.pipeline.runtime(&ctx);
+        let pager = Pager::from_resumable_callback(
+            pager_options.continuation_token,
+            move |continuation| {
+                // Then we have to clone it again to pass it in to the async block.
+                // This is because Pageable can't borrow any data, it has to own it all.
+                // That's probably good, because it means a Pageable can outlive the client that produced it, but it requires some extra cloning.
+                let pipeline = pipeline.clone();
+                let mut req = base_request.clone();
+                let ctx = ctx.clone();
+                async move {
+                    if let Some(continuation) = continuation {
+                        req.insert_header(constants::CONTINUATION, continuation);
+                    }
+
+                    let resp = pipeline.send(&ctx, &mut req).await?;
+
+                    Ok(PagerResult::from_response_header(
+                        resp,
+                        &constants::CONTINUATION,
+                    ))
                 }
-
-                let resp = pipeline.send(&ctx, &mut req).await?;
-
-                Ok(PagerResult::from_response_header(
-                    resp,
-                    &constants::CONTINUATION,
-                ))
-            }
-        }))
+            },
+        )?;
+        Ok(pager.with_prefetch(pager_options.prefetch, runtime))
     }
 
     /// Helper function to read a throughput offer given a resource ID.
@@ -131,6 +140,7 @@ impl CosmosPipeline {
             query,
             Request::new(self.url(&offers_link), Method::Post),
             offers_link.clone(),
+            PagerOptions::default(),
         )?;
         let offers = results
             .next()
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::credentials::cache::TokenCache;
+use crate::{credentials::cache::TokenCache, TokenCredentialOptions};
 use async_process::Command;
 use azure_core::{
     credentials::{AccessToken, Secret, TokenCredential},
@@ -162,6 +162,15 @@ impl AzureCliCredential {
         }))
     }
 
+    /// Create a new `AzureCliCredential` whose cached tokens expire by the clock in `options`.
+    pub fn with_options(
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            cache: TokenCache::with_clock(options.into().clock()),
+        }))
+    }
+
     /// Get an access token for an optional resource
     async fn get_access_token(scopes: Option<&[&str]>) -> azure_core::Result<CliTokenResponse> {
         // on window az is a cmd and it should be called like this
@@ -2,21 +2,35 @@
 // Licensed under the MIT License.
 
 use async_lock::RwLock;
-use azure_core::credentials::AccessToken;
+use azure_core::{
+    credentials::AccessToken,
+    date::{Clock, SystemClock},
+};
 use futures::Future;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use tracing::trace;
 
 #[derive(Debug)]
-pub(crate) struct TokenCache(RwLock<HashMap<Vec<String>, AccessToken>>);
+pub(crate) struct TokenCache {
+    tokens: RwLock<HashMap<Vec<String>, AccessToken>>,
+    clock: Arc<dyn Clock>,
+}
 
 impl TokenCache {
     pub(crate) fn new() -> Self {
-        Self(RwLock::new(HashMap::new()))
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a `TokenCache` that expires tokens by the current time from the `clock`.
+    pub(crate) fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            tokens: RwLock::new(HashMap::new()),
+            clock,
+        }
     }
 
     pub(crate) async fn clear(&self) -> azure_core::Result<()> {
-        let mut token_cache = self.0.write().await;
+        let mut token_cache = self.tokens.write().await;
         token_cache.clear();
         Ok(())
     }
@@ -27,10 +41,10 @@ impl TokenCache {
         callback: impl Future<Output = azure_core::Result<AccessToken>>,
     ) -> azure_core::Result<AccessToken> {
         // if the current cached token for this resource is good, return it.
-        let token_cache = self.0.read().await;
+        let token_cache = self.tokens.read().await;
         let scopes = scopes.iter().map(ToString::to_string).collect::<Vec<_>>();
         if let Some(token) = token_cache.get(&scopes) {
-            if !token.is_expired(None) {
+            if !token.is_expired_with_clock(self.clock.as_ref(), None) {
                 trace!("returning cached token");
                 return Ok(token.clone());
             }
@@ -38,12 +52,12 @@ impl TokenCache {
 
         // otherwise, drop the read lock and get a write lock to refresh the token
         drop(token_cache);
-        let mut token_cache = self.0.write().await;
+        let mut token_cache = self.tokens.write().await;
 
         // check again in case another thread refreshed the token while we were
         // waiting on the write lock
         if let Some(token) = token_cache.get(&scopes) {
-            if !token.is_expired(None) {
+            if !token.is_expired_with_clock(self.clock.as_ref(), None) {
                 trace!("returning token that was updated while waiting on write lock");
                 return Ok(token.clone());
             }
@@ -64,7 +78,7 @@ impl TokenCache {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use azure_core::credentials::Secret;
+    use azure_core::{credentials::Secret, date::ManualClock};
     use std::{sync::Mutex, time::Duration};
     use time::OffsetDateTime;
 
@@ -164,4 +178,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_expires_token_by_clock() -> azure_core::Result<()> {
+        let resource = &[STORAGE_TOKEN_SCOPE];
+        let clock = ManualClock::default();
+        let expires_on = clock.now() + Duration::from_secs(300);
+        let token_response = AccessToken::new(Secret::new("test-token"), expires_on);
+
+        let mock_credential = MockCredential::new(token_response);
+
+        let cache = TokenCache::with_clock(Arc::new(clock.clone()));
+
+        // The token is cached until it is within 30 seconds of expiring.
+        for (advance, expected) in [(0, 1), (240, 1), (60, 2)] {
+            clock.advance(Duration::from_secs(advance));
+            let token = cache
+                .get_token(resource, mock_credential.get_token(resource))
+                .await?;
+            assert_eq!(
+                token.token.secret(),
+                format!("{}-test-token:{}", resource.join(" "), expected)
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -123,7 +123,7 @@ impl ClientCertificateCredential {
             http_client: options.options().http_client().clone(),
             authority_host: options.options().authority_host()?.clone(),
             send_certificate_chain: options.send_certificate_chain(),
-            cache: TokenCache::new(),
+            cache: TokenCache::with_clock(options.options().clock()),
         }))
     }
 
@@ -4,12 +4,13 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::AzureCliCredential;
 use crate::{
-    credentials::cache::TokenCache, timeout::TimeoutExt, AppServiceManagedIdentityCredential,
-    ImdsId, TokenCredentialOptions, VirtualMachineManagedIdentityCredential,
+    credentials::cache::TokenCache, AppServiceManagedIdentityCredential, ImdsId,
+    TokenCredentialOptions, VirtualMachineManagedIdentityCredential,
 };
 use azure_core::{
     credentials::{AccessToken, TokenCredential},
     error::{Error, ErrorKind, ResultExt},
+    sleep::TimeoutExt,
 };
 use std::{sync::Arc, time::Duration};
 
@@ -121,7 +122,7 @@ impl DefaultAzureCredentialBuilder {
                 }
                 #[cfg(not(target_arch = "wasm32"))]
                 DefaultAzureCredentialType::AzureCli => {
-                    if let Ok(credential) = AzureCliCredential::new() {
+                    if let Ok(credential) = AzureCliCredential::with_options(self.options.clone()) {
                         sources.push(DefaultAzureCredentialKind::AzureCli(credential));
                     }
                 }
@@ -142,7 +143,7 @@ impl DefaultAzureCredentialBuilder {
     pub fn build(&self) -> azure_core::Result<Arc<DefaultAzureCredential>> {
         let included = self.included();
         let sources = self.create_sources(&included)?;
-        DefaultAzureCredential::with_sources(sources)
+        DefaultAzureCredential::with_sources(sources, &self.options)
     }
 }
 
@@ -251,10 +252,13 @@ impl DefaultAzureCredential {
     }
 
     /// Creates a `DefaultAzureCredential` with specified sources.
-    fn with_sources(sources: Vec<DefaultAzureCredentialKind>) -> azure_core::Result<Arc<Self>> {
+    fn with_sources(
+        sources: Vec<DefaultAzureCredentialKind>,
+        options: &TokenCredentialOptions,
+    ) -> azure_core::Result<Arc<Self>> {
         Ok(Arc::new(DefaultAzureCredential {
             sources,
-            cache: TokenCache::new(),
+            cache: TokenCache::with_clock(options.clock()),
         }))
     }
 
@@ -65,7 +65,7 @@ impl ImdsManagedIdentityCredential {
             secret_header: secret_header.to_owned(),
             secret_env: secret_env.to_owned(),
             id,
-            cache: TokenCache::new(),
+            cache: TokenCache::with_clock(options.clock()),
         }
     }
 
@@ -3,6 +3,7 @@
 
 use crate::env::Env;
 use azure_core::{
+    date::{Clock, SystemClock},
     error::{ErrorKind, Result, ResultExt},
     Url,
 };
@@ -18,6 +19,7 @@ pub struct TokenCredentialOptions {
     env: Env,
     http_client: Arc<dyn azure_core::HttpClient>,
     authority_host: String,
+    clock: Arc<dyn Clock>,
 }
 
 /// The default token credential options.
@@ -33,6 +35,7 @@ impl Default for TokenCredentialOptions {
             env: Env::default(),
             http_client: azure_core::new_http_client(),
             authority_host,
+            clock: Arc::new(SystemClock),
         }
     }
 }
@@ -55,6 +58,16 @@ impl TokenCredentialOptions {
         self.http_client.clone()
     }
 
+    /// Set the clock used to expire cached tokens.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// The clock used to expire cached tokens. The default is [`SystemClock`].
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
     pub(crate) fn env(&self) -> &Env {
         &self.env
     }
@@ -52,6 +52,23 @@ impl WorkloadIdentityCredential {
         }))
     }
 
+    /// Create a new `WorkloadIdentityCredential` whose cached tokens expire by the clock in `options`.
+    fn with_options(
+        options: &TokenCredentialOptions,
+        tenant_id: String,
+        client_id: String,
+        token: String,
+    ) -> azure_core::Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            http_client: options.http_client(),
+            authority_host: options.authority_host()?,
+            tenant_id,
+            client_id,
+            token: token.into(),
+            cache: TokenCache::with_clock(options.clock()),
+        }))
+    }
+
     /// Create a new `WorkloadIdentityCredential` from environment variables.
     ///
     /// # Variables
@@ -63,8 +80,6 @@ impl WorkloadIdentityCredential {
         options: impl Into<TokenCredentialOptions>,
     ) -> azure_core::Result<Arc<WorkloadIdentityCredential>> {
         let options = options.into();
-        let http_client = options.http_client();
-        let authority_host = options.authority_host()?;
         let env = options.env();
         let tenant_id =
             env.var(AZURE_TENANT_ID_ENV_KEY)
@@ -87,13 +102,7 @@ impl WorkloadIdentityCredential {
             .var(AZURE_FEDERATED_TOKEN)
             .map_kind(ErrorKind::Credential)
         {
-            return WorkloadIdentityCredential::new(
-                http_client,
-                authority_host,
-                tenant_id,
-                client_id,
-                token,
-            );
+            return WorkloadIdentityCredential::with_options(&options, tenant_id, client_id, token);
         }
 
         if let Ok(token_file) = env
@@ -109,13 +118,7 @@ impl WorkloadIdentityCredential {
                     )
                 },
             )?;
-            return WorkloadIdentityCredential::new(
-                http_client,
-                authority_host,
-                tenant_id,
-                client_id,
-                token,
-            );
+            return WorkloadIdentityCredential::with_options(&options, tenant_id, client_id, token);
         }
 
         Err(Error::with_message(ErrorKind::Credential, || {
@@ -9,6 +9,5 @@ mod env;
 mod federated_credentials_flow;
 mod oauth2_http_client;
 mod refresh_token;
-mod timeout;
 
 pub use crate::credentials::*;
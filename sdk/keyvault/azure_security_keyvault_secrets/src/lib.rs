@@ -7,7 +7,4 @@ mod generated;
 
 pub use generated::clients::secret_client::*;
 
-pub mod models {
-    pub use crate::generated::enums::*;
-    pub use crate::generated::models::*;
-}
+pub mod models;
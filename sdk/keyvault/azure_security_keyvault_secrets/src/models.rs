@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+pub use crate::generated::enums::*;
+pub use crate::generated::models::*;
+
+use azure_core::Page;
+
+impl Page for SecretListResult {
+    type Item = SecretItem;
+    type IntoIter = std::vec::IntoIter<SecretItem>;
+
+    fn into_items(self) -> Self::IntoIter {
+        self.value.unwrap_or_default().into_iter()
+    }
+}
+
+impl Page for DeletedSecretListResult {
+    type Item = DeletedSecretItem;
+    type IntoIter = std::vec::IntoIter<DeletedSecretItem>;
+
+    fn into_items(self) -> Self::IntoIter {
+        self.value.unwrap_or_default().into_iter()
+    }
+}
@@ -12,10 +12,25 @@ use std::fmt::{Debug, Display};
 /// A convenience alias for `Result` where the error type is hard coded to [`Error`].
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The HTTP status codes of responses that may succeed if the request is retried.
+///
+/// Requests that fail with any other 4xx or 5xx status code should not be retried.
+#[cfg(feature = "http")]
+pub const RETRY_STATUSES: &[StatusCode] = &[
+    StatusCode::RequestTimeout,
+    StatusCode::TooManyRequests,
+    StatusCode::InternalServerError,
+    StatusCode::BadGateway,
+    StatusCode::ServiceUnavailable,
+    StatusCode::GatewayTimeout,
+];
+
 /// The kind of error.
 ///
 /// The classification of error is intentionally fairly coarse.
+/// New kinds may be added, so matches on an `ErrorKind` must include a wildcard arm.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// An HTTP status code that was not expected.
     #[cfg(feature = "http")]
@@ -31,6 +46,10 @@ pub enum ErrorKind {
     Credential,
     /// An error having to do with the mock framework.
     MockFramework,
+    /// The operation was cancelled or its deadline passed.
+    Cancelled,
+    /// A request was not sent because the circuit for the host is open after consecutive failures.
+    CircuitOpen,
     /// A catch all for other kinds of errors.
     Other,
 }
@@ -63,6 +82,8 @@ impl Display for ErrorKind {
             ErrorKind::DataConversion => f.write_str("DataConversion"),
             ErrorKind::Credential => f.write_str("Credential"),
             ErrorKind::MockFramework => f.write_str("MockFramework"),
+            ErrorKind::Cancelled => f.write_str("Cancelled"),
+            ErrorKind::CircuitOpen => f.write_str("CircuitOpen"),
             ErrorKind::Other => f.write_str("Other"),
         }
     }
@@ -174,6 +195,47 @@ impl Error {
         }
     }
 
+    /// Returns `true` if the operation may succeed if it is retried.
+    ///
+    /// IO errors and HTTP responses with one of the [`RETRY_STATUSES`] are retryable.
+    pub fn is_retryable(&self) -> bool {
+        #[cfg(feature = "http")]
+        if let Some(status) = self.http_status() {
+            return RETRY_STATUSES.contains(&status);
+        }
+        matches!(self.kind(), ErrorKind::Io)
+    }
+
+    /// Returns `true` if the service throttled the request with status 429.
+    #[cfg(feature = "http")]
+    pub fn is_throttling(&self) -> bool {
+        self.http_status() == Some(StatusCode::TooManyRequests)
+    }
+
+    /// Returns `true` if a credential failed to get a token, or the service responded with status 401 or 403.
+    pub fn is_auth_failure(&self) -> bool {
+        #[cfg(feature = "http")]
+        if matches!(
+            self.http_status(),
+            Some(StatusCode::Unauthorized | StatusCode::Forbidden)
+        ) {
+            return true;
+        }
+        matches!(self.kind(), ErrorKind::Credential)
+    }
+
+    /// Returns `true` if the service responded with status 404.
+    #[cfg(feature = "http")]
+    pub fn is_not_found(&self) -> bool {
+        self.http_status() == Some(StatusCode::NotFound)
+    }
+
+    /// Returns `true` if the service responded with status 409.
+    #[cfg(feature = "http")]
+    pub fn is_conflict(&self) -> bool {
+        self.http_status() == Some(StatusCode::Conflict)
+    }
+
     /// Consumes the `Error`, returning its inner error, if any.
     pub fn into_inner(self) -> std::result::Result<Box<dyn std::error::Error + Send + Sync>, Self> {
         match self.context {
@@ -472,4 +534,27 @@ mod tests {
         let result = result.map_kind(ErrorKind::Io);
         assert_eq!(&ErrorKind::Io, result.unwrap_err().kind());
     }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn classifies_errors() {
+        let http = |status| ErrorKind::http_response(status, None).into_error();
+
+        assert!(http(StatusCode::TooManyRequests).is_retryable());
+        assert!(http(StatusCode::TooManyRequests).is_throttling());
+        assert!(http(StatusCode::ServiceUnavailable).is_retryable());
+        assert!(!http(StatusCode::ServiceUnavailable).is_throttling());
+        assert!(Error::message(ErrorKind::Io, "reset").is_retryable());
+        assert!(!http(StatusCode::NotFound).is_retryable());
+
+        assert!(http(StatusCode::NotFound).is_not_found());
+        assert!(http(StatusCode::Conflict).is_conflict());
+        assert!(http(StatusCode::Forbidden).is_auth_failure());
+        assert!(Error::message(ErrorKind::Credential, "no token").is_auth_failure());
+        assert!(!Error::message(ErrorKind::Other, "other").is_auth_failure());
+
+        // The kind of the outermost error is classified.
+        let err = http(StatusCode::Conflict).context("creating container");
+        assert!(err.is_conflict());
+    }
 }
@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::{AsyncRuntime, SleepFuture, TaskFuture, TaskHandle};
+use std::time::Duration;
+
+/// An [`AsyncRuntime`] that sleeps and spawns tasks using `async-std`.
+///
+/// Timers are driven by `async-io`, so this runtime can also be used with `smol`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsyncStdRuntime;
+
+impl AsyncRuntime for AsyncStdRuntime {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        Box::pin(::async_std::task::sleep(duration))
+    }
+
+    fn spawn(&self, future: TaskFuture) -> TaskHandle {
+        let task = ::async_std::task::spawn(future);
+        Box::pin(async move {
+            task.await;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[async_std::test]
+    async fn sleeps_and_spawns() {
+        let runtime = AsyncStdRuntime;
+        let start = Instant::now();
+        runtime.sleep(Duration::from_millis(10)).await;
+        assert!(start.elapsed() >= Duration::from_millis(10));
+
+        runtime.spawn(Box::pin(async {})).await.unwrap();
+    }
+}
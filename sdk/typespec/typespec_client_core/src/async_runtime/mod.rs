@@ -0,0 +1,113 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Async runtimes to sleep and spawn background tasks on.
+//!
+//! Clients use an [`AsyncRuntime`] to wait between retries and to run background tasks.
+//! Set [`ClientOptions::runtime`](crate::http::ClientOptions::runtime) to choose one;
+//! otherwise, [`default_runtime`] is used.
+
+#[cfg(all(feature = "async_std", not(target_arch = "wasm32")))]
+mod async_std;
+mod thread;
+#[cfg(not(target_arch = "wasm32"))]
+mod tokio;
+
+#[cfg(all(feature = "async_std", not(target_arch = "wasm32")))]
+pub use self::async_std::AsyncStdRuntime;
+pub use self::thread::ThreadRuntime;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::tokio::TokioRuntime;
+
+#[cfg(feature = "http")]
+use crate::http::Context;
+use std::{fmt::Debug, future::Future, pin::Pin, sync::Arc, time::Duration};
+
+/// A future returned by [`AsyncRuntime::sleep`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A future returned by [`AsyncRuntime::sleep`].
+#[cfg(target_arch = "wasm32")]
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// A future passed to [`AsyncRuntime::spawn`] to run in the background.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A future passed to [`AsyncRuntime::spawn`] to run in the background.
+#[cfg(target_arch = "wasm32")]
+pub type TaskFuture = Pin<Box<dyn Future<Output = ()> + 'static>>;
+
+/// A future returned by [`AsyncRuntime::spawn`] that completes when the spawned task completes.
+///
+/// Dropping a `TaskHandle` does not cancel the task.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TaskHandle = Pin<Box<dyn Future<Output = crate::Result<()>> + Send>>;
+
+/// A future returned by [`AsyncRuntime::spawn`] that completes when the spawned task completes.
+///
+/// Dropping a `TaskHandle` does not cancel the task.
+#[cfg(target_arch = "wasm32")]
+pub type TaskHandle = Pin<Box<dyn Future<Output = crate::Result<()>>>>;
+
+/// An async runtime to sleep and spawn background tasks on.
+pub trait AsyncRuntime: Debug + Send + Sync {
+    /// Creates a future that completes after `duration`.
+    fn sleep(&self, duration: Duration) -> SleepFuture;
+
+    /// Runs `future` in the background.
+    ///
+    /// Returns a [`TaskHandle`] that completes when `future` completes,
+    /// or returns an error if the task panicked or was cancelled by the runtime.
+    fn spawn(&self, future: TaskFuture) -> TaskHandle;
+}
+
+/// Gets the default [`AsyncRuntime`].
+///
+/// If called within a tokio runtime, a [`TokioRuntime`] for that runtime is returned;
+/// otherwise, a [`ThreadRuntime`] is returned.
+pub fn default_runtime() -> Arc<dyn AsyncRuntime> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(runtime) = TokioRuntime::current() {
+        return Arc::new(runtime);
+    }
+    Arc::new(ThreadRuntime)
+}
+
+/// Gets the [`AsyncRuntime`] in the `ctx`, or the [`default_runtime`] if there is none.
+#[cfg(feature = "http")]
+pub(crate) fn from_context(ctx: &Context) -> Arc<dyn AsyncRuntime> {
+    ctx.value::<Arc<dyn AsyncRuntime>>()
+        .cloned()
+        .unwrap_or_else(default_runtime)
+}
+
+/// An [`AsyncRuntime`] for tests that records the requested sleeps and completes them immediately.
+///
+/// Tasks are spawned on a [`ThreadRuntime`].
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct RecordingRuntime {
+    sleeps: std::sync::Mutex<Vec<Duration>>,
+}
+
+#[cfg(test)]
+impl RecordingRuntime {
+    /// Gets the durations of the requested sleeps in order.
+    pub(crate) fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl AsyncRuntime for RecordingRuntime {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        self.sleeps.lock().unwrap().push(duration);
+        Box::pin(std::future::ready(()))
+    }
+
+    fn spawn(&self, future: TaskFuture) -> TaskHandle {
+        ThreadRuntime.spawn(future)
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::{AsyncRuntime, SleepFuture, TaskFuture, TaskHandle};
+use futures::channel::oneshot;
+use std::{thread, time::Duration};
+use typespec::error::{Error, ErrorKind};
+
+/// An [`AsyncRuntime`] that uses an operating system thread for each sleep and spawned task.
+///
+/// This works with any executor but is less efficient than a runtime-specific implementation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ThreadRuntime;
+
+impl AsyncRuntime for ThreadRuntime {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        Box::pin(crate::sleep::thread::sleep(duration))
+    }
+
+    fn spawn(&self, future: TaskFuture) -> TaskHandle {
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            futures::executor::block_on(future);
+            let _ = tx.send(());
+        });
+        Box::pin(async move {
+            rx.await
+                .map_err(|_| Error::message(ErrorKind::Other, "spawned task panicked"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn sleeps_and_spawns() {
+        let runtime = ThreadRuntime;
+        let start = Instant::now();
+        futures::executor::block_on(runtime.sleep(Duration::from_millis(10)));
+        assert!(start.elapsed() >= Duration::from_millis(10));
+
+        let task = runtime.spawn(Box::pin(async {}));
+        futures::executor::block_on(task).unwrap();
+
+        let task = runtime.spawn(Box::pin(async { panic!("task failed") }));
+        assert!(futures::executor::block_on(task).is_err());
+    }
+}
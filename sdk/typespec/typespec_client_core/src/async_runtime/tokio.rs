@@ -0,0 +1,77 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::{AsyncRuntime, SleepFuture, TaskFuture, TaskHandle};
+use ::tokio::runtime::Handle;
+use std::time::Duration;
+use typespec::error::{Error, ErrorKind};
+
+/// An [`AsyncRuntime`] that sleeps and spawns tasks on a tokio runtime.
+///
+/// The tokio runtime must have its time driver enabled.
+#[derive(Clone, Debug)]
+pub struct TokioRuntime {
+    handle: Handle,
+}
+
+impl TokioRuntime {
+    /// Creates a `TokioRuntime` for the tokio runtime with the given `handle`.
+    pub fn new(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    /// Creates a `TokioRuntime` for the current tokio runtime, or `None` if not called within a tokio runtime.
+    pub fn current() -> Option<Self> {
+        Handle::try_current().ok().map(Self::new)
+    }
+}
+
+impl From<Handle> for TokioRuntime {
+    fn from(handle: Handle) -> Self {
+        Self::new(handle)
+    }
+}
+
+impl AsyncRuntime for TokioRuntime {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        // Register the timer with this runtime even if the future is polled elsewhere.
+        let _guard = self.handle.enter();
+        Box::pin(::tokio::time::sleep(duration))
+    }
+
+    fn spawn(&self, future: TaskFuture) -> TaskHandle {
+        let task = self.handle.spawn(future);
+        Box::pin(async move {
+            task.await
+                .map_err(|error| Error::full(ErrorKind::Other, error, "spawned task failed"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[tokio::test]
+    async fn spawns_on_current_runtime() {
+        let runtime = TokioRuntime::current().expect("tokio runtime");
+        let ran = Arc::new(AtomicBool::new(false));
+        let task = {
+            let ran = ran.clone();
+            runtime.spawn(Box::pin(async move {
+                ran.store(true, Ordering::SeqCst);
+            }))
+        };
+        task.await.unwrap();
+        assert!(ran.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn not_current_outside_runtime() {
+        assert!(TokioRuntime::current().is_none());
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+#[cfg(feature = "http")]
+use crate::http::Context;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use time::OffsetDateTime;
+
+/// A source of the current time.
+///
+/// Clients use a `Clock` to decide when retries expire, how long to wait for a `retry-after` date,
+/// and when access tokens need to be refreshed. Use a [`ManualClock`] to test time-dependent behavior
+/// without waiting.
+pub trait Clock: Debug + Send + Sync {
+    /// Gets the current time.
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// A [`Clock`] that returns the system time using [`OffsetDateTime::now_utc`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A [`Clock`] whose time only changes when it is set or advanced.
+///
+/// Cloning a `ManualClock` shares the same time.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Arc<Mutex<OffsetDateTime>>,
+}
+
+impl ManualClock {
+    /// Creates a `ManualClock` starting at `now`.
+    pub fn new(now: OffsetDateTime) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Sets the current time.
+    pub fn set(&self, now: OffsetDateTime) {
+        *self.now.lock().expect("lock poisoned") = now;
+    }
+
+    /// Advances the current time by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().expect("lock poisoned") += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new(OffsetDateTime::now_utc())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> OffsetDateTime {
+        *self.now.lock().expect("lock poisoned")
+    }
+}
+
+/// Gets the [`Clock`] in the `ctx`, or a [`SystemClock`] if there is none.
+#[cfg(feature = "http")]
+pub(crate) fn clock_from_context(ctx: &Context) -> Arc<dyn Clock> {
+    ctx.value::<Arc<dyn Clock>>()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(SystemClock))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_clock_advances() {
+        let start = OffsetDateTime::now_utc();
+        let clock = ManualClock::new(start);
+        let shared = clock.clone();
+        shared.advance(Duration::from_secs(10));
+        assert_eq!(start + Duration::from_secs(10), clock.now());
+
+        clock.set(start);
+        assert_eq!(start, shared.now());
+    }
+}
@@ -16,6 +16,9 @@ use typespec::error::{ErrorKind, ResultExt};
 pub use time::serde::rfc3339;
 pub use time::serde::timestamp;
 
+mod clock;
+pub use clock::*;
+
 // RFC 3339 vs ISO 8601: <https://ijmacd.github.io/rfc3339-iso8601/>
 pub mod iso8601;
 pub mod rfc7231;
@@ -74,6 +74,20 @@ impl HttpError {
         self.details.message.as_deref()
     }
 
+    /// Get the ID the service assigned to the request from the `x-ms-request-id` header, if any.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers
+            .get(headers::REQUEST_ID.as_str())
+            .map(String::as_str)
+    }
+
+    /// Get the ID the client assigned to the request from the `x-ms-client-request-id` header, if returned by the service.
+    pub fn client_request_id(&self) -> Option<&str> {
+        self.headers
+            .get(headers::CLIENT_REQUEST_ID.as_str())
+            .map(String::as_str)
+    }
+
     /// Get a reference to the HTTP error's headers.
     ///
     /// You should not display these headers directly.
@@ -214,6 +228,23 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn request_ids() {
+        use crate::http::headers::{Headers, CLIENT_REQUEST_ID, REQUEST_ID};
+
+        let mut headers = Headers::new();
+        headers.insert(REQUEST_ID, "abcd1234");
+        headers.insert(CLIENT_REQUEST_ID, "my-request");
+        let response: Response<()> =
+            Response::from_bytes(StatusCode::InternalServerError, headers, Bytes::new());
+        assert_eq!(Some("abcd1234"), response.request_id());
+        assert_eq!(Some("my-request"), response.client_request_id());
+
+        let err = HttpError::new(response).await;
+        assert_eq!(Some("abcd1234"), err.request_id());
+        assert_eq!(Some("my-request"), err.client_request_id());
+    }
+
     #[test]
     fn debug_is_sanitized() {
         let err = HttpError {
@@ -0,0 +1,349 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Blocking wrappers for calling clients from synchronous code.
+//!
+//! A [`BlockingRuntime`] owns a private async runtime and runs futures to completion on the calling thread,
+//! so that synchronous applications can call client methods without depending on an async runtime themselves.
+//!
+//! The blocking methods in this module must not be called from within an async runtime, including from within
+//! the future passed to [`BlockingRuntime::block_on`]; doing so panics.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use typespec_client_core::http::{blocking::BlockingPipeline, ClientOptions, Context, Method, Pipeline, Request};
+//! # fn main() -> typespec_client_core::Result<()> {
+//! let pipeline = BlockingPipeline::new(Pipeline::new(ClientOptions::default(), Vec::new(), Vec::new()))?;
+//!
+//! let mut request = Request::new("https://example.com".parse()?, Method::Get);
+//! let response = pipeline.send::<()>(&Context::new(), &mut request)?;
+//! let body = response.into_string()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    http::{
+        headers::Headers, response::ResponseBody, Context, Model, Pager, Pipeline, Request,
+        Response, StatusCode,
+    },
+    Bytes,
+};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use std::{fmt, future::Future, sync::Arc};
+use typespec::error::{ErrorKind, Result, ResultExt};
+
+/// A private async runtime that runs futures to completion on the calling thread.
+///
+/// Cloning a `BlockingRuntime` shares the same runtime.
+#[derive(Clone)]
+pub struct BlockingRuntime {
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingRuntime {
+    /// Creates a new `BlockingRuntime`.
+    pub fn new() -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context(ErrorKind::Io, "failed to create blocking runtime")?;
+        Ok(Self {
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Runs `future` to completion, blocking the current thread.
+    ///
+    /// Use this to call any async client method from synchronous code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Wraps `response` so that its body can be read without `.await`.
+    pub fn response<T>(&self, response: Response<T>) -> BlockingResponse<T> {
+        BlockingResponse {
+            response,
+            runtime: self.clone(),
+        }
+    }
+
+    /// Wraps `pager` in an [`Iterator`] that fetches each page when it is needed.
+    pub fn pages<T>(&self, pager: Pager<T>) -> BlockingPager<T> {
+        BlockingPager {
+            pager,
+            runtime: self.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for BlockingRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingRuntime").finish_non_exhaustive()
+    }
+}
+
+/// A [`Pipeline`] that sends requests synchronously.
+#[derive(Clone, Debug)]
+pub struct BlockingPipeline {
+    pipeline: Pipeline,
+    runtime: BlockingRuntime,
+}
+
+impl BlockingPipeline {
+    /// Creates a `BlockingPipeline` that sends requests through `pipeline` on a new private runtime.
+    pub fn new(pipeline: Pipeline) -> Result<Self> {
+        Ok(Self::with_runtime(pipeline, BlockingRuntime::new()?))
+    }
+
+    /// Creates a `BlockingPipeline` that sends requests through `pipeline` on an existing `runtime`.
+    pub fn with_runtime(pipeline: Pipeline, runtime: BlockingRuntime) -> Self {
+        Self { pipeline, runtime }
+    }
+
+    /// Gets the underlying [`Pipeline`].
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Gets the runtime requests are sent on.
+    pub fn runtime(&self) -> &BlockingRuntime {
+        &self.runtime
+    }
+
+    /// Sends `request` through the pipeline, blocking until the response headers are received.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    pub fn send<T>(&self, ctx: &Context<'_>, request: &mut Request) -> Result<BlockingResponse<T>> {
+        let response = self.runtime.block_on(self.pipeline.send(ctx, request))?;
+        Ok(self.runtime.response(response))
+    }
+}
+
+/// A [`Response`] whose body is read synchronously.
+pub struct BlockingResponse<T> {
+    response: Response<T>,
+    runtime: BlockingRuntime,
+}
+
+impl<T> BlockingResponse<T> {
+    /// Get the status code from the response.
+    pub fn status(&self) -> StatusCode {
+        self.response.status()
+    }
+
+    /// Get the headers from the response.
+    pub fn headers(&self) -> &Headers {
+        self.response.headers()
+    }
+
+    /// Gets the underlying [`Response`].
+    pub fn into_inner(self) -> Response<T> {
+        self.response
+    }
+
+    /// Reads the entire body of the response.
+    pub fn into_bytes(self) -> Result<Bytes> {
+        self.runtime
+            .block_on(self.response.into_raw_body().collect())
+    }
+
+    /// Reads the entire body of the response as a UTF-8 string.
+    pub fn into_string(self) -> Result<String> {
+        self.runtime
+            .block_on(self.response.into_raw_body().collect_string())
+    }
+
+    /// Reads the entire body of the response and deserializes it from JSON.
+    #[cfg(feature = "json")]
+    pub fn into_json_body<U: DeserializeOwned>(self) -> Result<U> {
+        self.runtime.block_on(self.response.into_json_body())
+    }
+
+    /// Reads the entire body of the response and deserializes it from XML.
+    #[cfg(feature = "xml")]
+    pub fn into_xml_body<U: DeserializeOwned>(self) -> Result<U> {
+        self.runtime.block_on(self.response.into_xml_body())
+    }
+
+    /// Gets an [`Iterator`] over the chunks of the response body as they are received.
+    pub fn into_chunks(self) -> BlockingBody {
+        BlockingBody {
+            body: self.response.into_raw_body(),
+            runtime: self.runtime,
+        }
+    }
+}
+
+impl<T: Model> BlockingResponse<T> {
+    /// Reads the entire body of the response and deserializes it into the model `T`.
+    pub fn into_body(self) -> Result<T> {
+        self.runtime.block_on(self.response.into_body())
+    }
+}
+
+impl<T> fmt::Debug for BlockingResponse<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingResponse")
+            .field("response", &self.response)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An [`Iterator`] over the chunks of a response body.
+///
+/// Created by [`BlockingResponse::into_chunks`].
+pub struct BlockingBody {
+    body: ResponseBody,
+    runtime: BlockingRuntime,
+}
+
+impl Iterator for BlockingBody {
+    type Item = Result<Bytes>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.body.next())
+    }
+}
+
+impl fmt::Debug for BlockingBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingBody").finish_non_exhaustive()
+    }
+}
+
+/// An [`Iterator`] over the pages of a [`Pager`].
+///
+/// Created by [`BlockingRuntime::pages`].
+pub struct BlockingPager<T> {
+    pager: Pager<T>,
+    runtime: BlockingRuntime,
+}
+
+impl<T> BlockingPager<T> {
+    /// Gets an [`Iterator`] that reads each page and deserializes it into the model `T`.
+    pub fn into_bodies(self) -> impl Iterator<Item = Result<T>>
+    where
+        T: Model,
+    {
+        let runtime = self.runtime.clone();
+        self.map(move |page| runtime.block_on(page?.into_body()))
+    }
+}
+
+impl<T> Iterator for BlockingPager<T> {
+    type Item = Result<Response<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.pager.next())
+    }
+}
+
+impl<T> fmt::Debug for BlockingPager<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingPager").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        policies::{Policy, PolicyResult},
+        ClientOptions, Method, PagerResult, TransportOptions,
+    };
+    use serde::Deserialize;
+    use typespec_macros::Model;
+
+    #[derive(Debug)]
+    struct Responder;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Responder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let page: usize = request
+                .url()
+                .query_pairs()
+                .find_map(|(name, value)| (name == "page").then(|| value.parse().unwrap()))
+                .unwrap_or(1);
+            Ok(Response::from_bytes(
+                StatusCode::Ok,
+                Headers::new(),
+                format!(r#"{{"page":{page}}}"#),
+            ))
+        }
+    }
+
+    #[derive(Debug, Deserialize, Model, PartialEq)]
+    #[typespec(crate = "crate")]
+    struct Page {
+        page: usize,
+    }
+
+    fn pipeline() -> BlockingPipeline {
+        let options = ClientOptions {
+            transport: Some(TransportOptions::new_custom_policy(Arc::new(Responder))),
+            ..Default::default()
+        };
+        BlockingPipeline::new(Pipeline::new(options, Vec::new(), Vec::new())).unwrap()
+    }
+
+    #[test]
+    fn sends_request() {
+        let pipeline = pipeline();
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let response = pipeline
+            .send::<Page>(&Context::new(), &mut request)
+            .unwrap();
+        assert_eq!(StatusCode::Ok, response.status());
+        assert_eq!(Page { page: 1 }, response.into_body().unwrap());
+    }
+
+    #[test]
+    fn iterates_pages() {
+        let pipeline = pipeline();
+        let inner = pipeline.pipeline().clone();
+        let pager: Pager<Page> = Pager::from_callback(move |continuation: Option<usize>| {
+            let pipeline = inner.clone();
+            async move {
+                let page = continuation.unwrap_or(1);
+                let url = format!("http://localhost?page={page}").parse().unwrap();
+                let mut request = Request::new(url, Method::Get);
+                let response = pipeline.send(&Context::new(), &mut request).await?;
+                Ok(if page < 3 {
+                    PagerResult::Continue {
+                        response,
+                        continuation: page + 1,
+                    }
+                } else {
+                    PagerResult::Complete { response }
+                })
+            }
+        });
+
+        let pages: Vec<Page> = pipeline
+            .runtime()
+            .pages(pager)
+            .into_bodies()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            vec![Page { page: 1 }, Page { page: 2 }, Page { page: 3 }],
+            pages
+        );
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    async_runtime::{self, AsyncRuntime},
+    date::{self, clock_from_context, Clock, OffsetDateTime, SystemClock},
+    http::Context,
+};
+use futures::future::{self, Either};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Waker},
+    time::Duration,
+};
+use typespec::error::{Error, ErrorKind, Result};
+
+/// A point in time after which an operation should no longer be attempted.
+///
+/// Add a `Deadline` to the [`Context`] passed to a client method to stop retrying, abort in-flight requests,
+/// and stop fetching pages once the deadline has passed.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use typespec_client_core::http::{Context, Deadline};
+/// let context = Context::new().with_value(Deadline::after(Duration::from_secs(5)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Deadline(OffsetDateTime);
+
+impl Deadline {
+    /// Creates a deadline at the given time.
+    pub fn at(time: OffsetDateTime) -> Self {
+        Self(time)
+    }
+
+    /// Creates a deadline after the given duration from now.
+    pub fn after(duration: Duration) -> Self {
+        Self::after_with_clock(duration, &SystemClock)
+    }
+
+    /// Creates a deadline after the given duration from the current time from the `clock`.
+    pub fn after_with_clock(duration: Duration, clock: &dyn Clock) -> Self {
+        Self(clock.now() + duration)
+    }
+
+    /// Gets the time of the deadline.
+    pub fn time(&self) -> OffsetDateTime {
+        self.0
+    }
+
+    /// Gets the time remaining until the deadline, or `None` if the deadline has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining_with_clock(&SystemClock)
+    }
+
+    /// Gets the time remaining until the deadline from the current time from the `clock`,
+    /// or `None` if the deadline has passed.
+    pub fn remaining_with_clock(&self, clock: &dyn Clock) -> Option<Duration> {
+        let now = clock.now();
+        (self.0 > now).then(|| date::diff(self.0, now))
+    }
+
+    /// Returns `true` if the deadline has passed; otherwise, `false`.
+    pub fn has_passed(&self) -> bool {
+        self.remaining().is_none()
+    }
+
+    /// Returns `true` if the deadline has passed at the current time from the `clock`; otherwise, `false`.
+    pub fn has_passed_with_clock(&self, clock: &dyn Clock) -> bool {
+        self.remaining_with_clock(clock).is_none()
+    }
+}
+
+impl From<OffsetDateTime> for Deadline {
+    fn from(time: OffsetDateTime) -> Self {
+        Self::at(time)
+    }
+}
+
+impl From<Duration> for Deadline {
+    fn from(duration: Duration) -> Self {
+        Self::after(duration)
+    }
+}
+
+/// A token to cancel an operation.
+///
+/// Add a clone of the token to the [`Context`] passed to a client method and call [`CancellationToken::cancel`]
+/// to stop retrying, abort in-flight requests, and stop fetching pages.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationTokenInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationTokenInner {
+    cancelled: AtomicBool,
+    wakers: Mutex<Wakers>,
+}
+
+/// The wakers of the futures waiting for a [`CancellationToken`], keyed so each future can remove its own.
+#[derive(Debug, Default)]
+struct Wakers {
+    next_key: u64,
+    wakers: HashMap<u64, Waker>,
+}
+
+/// Registers the waker of a future waiting for a [`CancellationToken`], and removes it when the future is dropped.
+struct Registration<'a> {
+    inner: &'a CancellationTokenInner,
+    key: Option<u64>,
+}
+
+impl Registration<'_> {
+    fn register(&mut self, waker: &Waker) {
+        let mut wakers = self.inner.wakers.lock().expect("lock poisoned");
+        if let Some(registered) = self.key.and_then(|key| wakers.wakers.get_mut(&key)) {
+            if !registered.will_wake(waker) {
+                registered.clone_from(waker);
+            }
+            return;
+        }
+        let key = wakers.next_key;
+        wakers.next_key += 1;
+        wakers.wakers.insert(key, waker.clone());
+        self.key = Some(key);
+    }
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.inner
+                .wakers
+                .lock()
+                .expect("lock poisoned")
+                .wakers
+                .remove(&key);
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new `CancellationToken`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all operations using this token or any of its clones.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        let wakers = std::mem::take(&mut self.inner.wakers.lock().expect("lock poisoned").wakers);
+        wakers.into_values().for_each(Waker::wake);
+    }
+
+    /// Returns `true` if the token was cancelled; otherwise, `false`.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Waits until the token is cancelled.
+    pub async fn cancelled(&self) {
+        let mut registration = Registration {
+            inner: &self.inner,
+            key: None,
+        };
+        future::poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            registration.register(cx.waker());
+            // Check again in case the token was cancelled while registering the waker.
+            if self.is_cancelled() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+/// The [`Deadline`] and [`CancellationToken`] of an operation, if any.
+#[derive(Clone, Debug)]
+pub(crate) struct Cancellation {
+    deadline: Option<Deadline>,
+    token: Option<CancellationToken>,
+    runtime: Arc<dyn AsyncRuntime>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Cancellation {
+    pub(crate) fn from_context(ctx: &Context) -> Self {
+        Self {
+            deadline: ctx.value::<Deadline>().copied(),
+            token: ctx.value::<CancellationToken>().cloned(),
+            runtime: async_runtime::from_context(ctx),
+            clock: clock_from_context(ctx),
+        }
+    }
+
+    pub(crate) fn is_none(&self) -> bool {
+        self.deadline.is_none() && self.token.is_none()
+    }
+
+    /// Returns an error if the operation was cancelled or its deadline passed.
+    pub(crate) fn check(&self) -> Result<()> {
+        if self
+            .token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(cancelled_error());
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| deadline.has_passed_with_clock(self.clock.as_ref()))
+        {
+            return Err(deadline_error());
+        }
+        Ok(())
+    }
+
+    /// Runs `future` until it completes, the operation is cancelled, or its deadline passes.
+    ///
+    /// If the operation is cancelled or its deadline passes, `future` is dropped and an error is returned.
+    pub(crate) async fn run<F, T>(&self, future: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        if self.is_none() {
+            return future.await;
+        }
+        self.check()?;
+
+        let deadline = async {
+            let remaining = self
+                .deadline
+                .and_then(|deadline| deadline.remaining_with_clock(self.clock.as_ref()));
+            match remaining {
+                Some(remaining) => self.runtime.sleep(remaining).await,
+                None => future::pending().await,
+            }
+        };
+        let cancelled = async {
+            match &self.token {
+                Some(token) => token.cancelled().await,
+                None => future::pending().await,
+            }
+        };
+        let (deadline, cancelled) = (pin!(deadline), pin!(cancelled));
+        let cancelled = future::select(deadline, cancelled);
+
+        match future::select(pin!(future), cancelled).await {
+            Either::Left((result, _)) => result,
+            Either::Right((Either::Left(_), _)) => Err(deadline_error()),
+            Either::Right((Either::Right(_), _)) => Err(cancelled_error()),
+        }
+    }
+}
+
+fn cancelled_error() -> Error {
+    Error::message(ErrorKind::Cancelled, "the operation was cancelled")
+}
+
+fn deadline_error() -> Error {
+    Error::message(
+        ErrorKind::Cancelled,
+        "the deadline for the operation has passed",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ManualClock;
+
+    #[test]
+    fn deadline_remaining() {
+        assert!(Deadline::after(Duration::from_secs(60))
+            .remaining()
+            .is_some());
+        assert!(Deadline::at(OffsetDateTime::now_utc() - Duration::from_secs(1)).has_passed());
+    }
+
+    #[test]
+    fn deadline_uses_context_clock() {
+        let clock = ManualClock::default();
+        let deadline = Deadline::after_with_clock(Duration::from_secs(60), &clock);
+        let ctx = Context::new()
+            .with_value(deadline)
+            .with_value::<Arc<dyn Clock>>(Arc::new(clock.clone()));
+        assert!(Cancellation::from_context(&ctx).check().is_ok());
+
+        clock.advance(Duration::from_secs(60));
+        assert!(Cancellation::from_context(&ctx).check().is_err());
+    }
+
+    #[tokio::test]
+    async fn dropped_waits_unregister() {
+        let token = CancellationToken::new();
+        for _ in 0..10 {
+            let ctx = Context::new().with_value(token.clone());
+            Cancellation::from_context(&ctx)
+                .run(async {
+                    tokio::task::yield_now().await;
+                    Ok(())
+                })
+                .await
+                .unwrap();
+        }
+        assert!(token.inner.wakers.lock().unwrap().wakers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_completes() {
+        let ctx = Context::new()
+            .with_value(Deadline::after(Duration::from_secs(60)))
+            .with_value(CancellationToken::new());
+        let result = Cancellation::from_context(&ctx).run(async { Ok(1) }).await;
+        assert_eq!(1, result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn run_stops_at_deadline() {
+        let ctx = Context::new().with_value(Deadline::after(Duration::from_millis(10)));
+        let err = Cancellation::from_context(&ctx)
+            .run(future::pending::<Result<()>>())
+            .await
+            .unwrap_err();
+        assert_eq!(&ErrorKind::Cancelled, err.kind());
+    }
+
+    #[tokio::test]
+    async fn run_stops_when_cancelled() {
+        let token = CancellationToken::new();
+        let ctx = Context::new().with_value(token.clone());
+        let cancellation = Cancellation::from_context(&ctx);
+
+        let handle =
+            tokio::spawn(async move { cancellation.run(future::pending::<Result<()>>()).await });
+        tokio::task::yield_now().await;
+        token.cancel();
+
+        let err = handle.await.unwrap().unwrap_err();
+        assert_eq!(&ErrorKind::Cancelled, err.kind());
+        assert!(Cancellation::from_context(&ctx).check().is_err());
+    }
+}
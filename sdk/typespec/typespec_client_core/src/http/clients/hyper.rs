@@ -0,0 +1,745 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    base64,
+    http::{
+        headers::{HeaderName, HeaderValue, Headers, PROXY_AUTHORIZATION},
+        request::{Body, Request},
+        response::PinnedStream,
+        HttpClient, HttpClientOptions, ProxyOptions, Response, StatusCode, Url,
+    },
+    stream::SeekableStream,
+    Bytes,
+};
+use async_trait::async_trait;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use http_body::{Body as _, Frame, SizeHint};
+use http_body_util::{combinators::BoxBody, BodyExt, BodyStream};
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+    client::legacy::{
+        connect::{Connected, Connection, HttpConnector},
+        Client,
+    },
+    rt::{TokioExecutor, TokioIo},
+};
+use rustls::{
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    ClientConfig, RootCertStore,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::Instant,
+};
+use tower_service::Service;
+use tracing::{debug, warn};
+use typespec::error::{Error, ErrorKind, Result, ResultExt};
+
+/// How long an idle pooled connection is kept open by default.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// The maximum size of each chunk of a request body sent from bytes.
+const REQUEST_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The maximum size of a proxy's response to a `CONNECT` request.
+const MAX_CONNECT_RESPONSE_SIZE: usize = 8192;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Create a new [`HttpClient`] with the `hyper` backend.
+///
+/// Connections are pooled per host and kept open for reuse. HTTPS connections trust the Mozilla root certificates.
+pub fn new_hyper_client() -> Arc<dyn HttpClient> {
+    new_hyper_client_with_options(&HttpClientOptions::default())
+        .expect("failed to build `hyper` client")
+}
+
+/// Create a new [`HttpClient`] with the `hyper` backend configured with the given options.
+///
+/// If no proxy is set in the `options`, proxies are read from the environment using [`ProxyOptions::from_env`].
+/// Proxies are connected to using HTTP; `https` requests are tunneled through the proxy using `CONNECT`.
+pub fn new_hyper_client_with_options(options: &HttpClientOptions) -> Result<Arc<dyn HttpClient>> {
+    debug!("instantiating an http client using the hyper backend");
+
+    let proxy = Arc::new(options.proxy_or_env());
+    for proxy in [&proxy.http_proxy, &proxy.https_proxy]
+        .into_iter()
+        .flatten()
+    {
+        if proxy.scheme() != "http" {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "proxy scheme `{}` is not supported by the `hyper` backend",
+                    proxy.scheme()
+                )
+            }));
+        }
+    }
+
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+    http.set_connect_timeout(options.connect_timeout);
+    let connector = ProxyConnector {
+        http,
+        proxy: proxy.clone(),
+    };
+
+    let builder = HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config(options)?)
+        .https_or_http()
+        .enable_http1();
+    let connector = if options.prefer_http2 {
+        builder.enable_http2().wrap_connector(connector)
+    } else {
+        builder.wrap_connector(connector)
+    };
+
+    let mut builder = Client::builder(TokioExecutor::new());
+    builder.pool_idle_timeout(options.pool_idle_timeout.unwrap_or(POOL_IDLE_TIMEOUT));
+    if let Some(pool_max_idle_per_host) = options.pool_max_idle_per_host {
+        builder.pool_max_idle_per_host(pool_max_idle_per_host);
+    }
+
+    Ok(Arc::new(HyperClient {
+        client: builder.build(connector),
+        proxy,
+        read_timeout: options.read_timeout,
+    }))
+}
+
+fn tls_config(options: &HttpClientOptions) -> Result<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    for pem in &options.root_certificates {
+        for certificate in CertificateDer::pem_slice_iter(pem) {
+            let certificate = certificate.context(
+                ErrorKind::DataConversion,
+                "failed to parse root certificates",
+            )?;
+            roots
+                .add(certificate)
+                .context(ErrorKind::DataConversion, "failed to add root certificate")?;
+        }
+    }
+
+    let builder =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .context(ErrorKind::Other, "failed to configure TLS")?
+            .with_root_certificates(roots);
+    let config = match &options.client_certificate {
+        Some(client_certificate) => {
+            let chain = CertificateDer::pem_slice_iter(&client_certificate.certificate_chain)
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .context(
+                    ErrorKind::DataConversion,
+                    "failed to parse client certificate",
+                )?;
+            let key = PrivateKeyDer::from_pem_slice(&client_certificate.private_key).context(
+                ErrorKind::DataConversion,
+                "failed to parse client certificate key",
+            )?;
+            builder
+                .with_client_auth_cert(chain, key)
+                .context(ErrorKind::DataConversion, "invalid client certificate")?
+        }
+        None => builder.with_no_client_auth(),
+    };
+    Ok(config)
+}
+
+type HyperBody = BoxBody<Bytes, Error>;
+
+/// An [`HttpClient`] using a pooled `hyper` client.
+#[derive(Clone)]
+struct HyperClient {
+    client: Client<HttpsConnector<ProxyConnector>, HyperBody>,
+    proxy: Arc<ProxyOptions>,
+    read_timeout: Option<Duration>,
+}
+
+impl std::fmt::Debug for HyperClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HyperClient")
+            .field("proxy", &self.proxy)
+            .field("read_timeout", &self.read_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl HttpClient for HyperClient {
+    async fn execute_request(&self, request: &Request) -> Result<Response> {
+        let method = request.method();
+        let mut req = ::http::Request::builder()
+            .method(method.as_ref())
+            .uri(request.url().as_str());
+        for (name, value) in request.headers().iter() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
+        // Requests to `http` URLs are sent to the proxy, which must be authorized on each request.
+        if request.url().scheme() == "http" {
+            if let Some(authorization) = self
+                .proxy
+                .proxy_for(request.url())
+                .and_then(proxy_authorization)
+            {
+                req = req.header(PROXY_AUTHORIZATION.as_str(), authorization);
+            }
+        }
+
+        let body = match request.body().clone() {
+            Body::Bytes(bytes) => BytesBody(bytes).boxed(),
+            Body::SeekableStream(stream) => SeekableStreamBody {
+                remaining: stream.len() as u64,
+                stream,
+            }
+            .boxed(),
+        };
+        let sent = SentAt::default();
+        let body = SentBody::new(body, sent.clone()).boxed();
+        let req = req
+            .body(body)
+            .context(ErrorKind::Other, "failed to build `hyper` request")?;
+
+        debug!("performing request {method} with `hyper`");
+        let rsp = self.client.request(req);
+        let rsp = match self.read_timeout {
+            Some(read_timeout) => with_response_timeout(rsp, &sent, read_timeout).await?,
+            None => rsp.await,
+        }
+        .context(ErrorKind::Io, "failed to execute `hyper` request")?;
+
+        let status = rsp.status().as_u16();
+        let status = StatusCode::try_from(status).map_err(|_| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid status code {status}")
+            })
+        })?;
+        let headers = to_headers(rsp.headers());
+
+        let body = BodyStream::new(rsp.into_body())
+            .try_filter_map(|frame| futures::future::ready(Ok(frame.into_data().ok())))
+            .map_err(|error| {
+                Error::full(
+                    ErrorKind::Io,
+                    error,
+                    "error converting `hyper` response into a byte stream",
+                )
+            });
+        let body: PinnedStream = match self.read_timeout {
+            Some(read_timeout) => Box::pin(with_read_timeout(body, read_timeout)),
+            None => Box::pin(body),
+        };
+
+        Ok(Response::new(status, headers, body))
+    }
+}
+
+/// Waits for the `response`, failing if it does not start within `read_timeout` of the request body being `sent`.
+///
+/// Sending the request body is not subject to the timeout, however long it takes.
+async fn with_response_timeout<F: Future>(
+    response: F,
+    sent: &SentAt,
+    read_timeout: Duration,
+) -> Result<F::Output> {
+    let mut response = pin!(response);
+    loop {
+        let sent_at = *sent.lock().expect("lock poisoned");
+        let deadline = sent_at.unwrap_or_else(Instant::now) + read_timeout;
+        match tokio::time::timeout_at(deadline, &mut response).await {
+            Ok(response) => return Ok(response),
+            Err(_) if sent_at.is_some() => {
+                return Err(Error::message(
+                    ErrorKind::Io,
+                    "timed out waiting for the `hyper` response",
+                ))
+            }
+            // The body was still being sent when the deadline passed, so wait again from when it was sent.
+            Err(_) => {}
+        }
+    }
+}
+
+/// Fails the `stream`, and stops reading it, if any read does not complete within `read_timeout`.
+fn with_read_timeout<S>(stream: S, read_timeout: Duration) -> impl Stream<Item = Result<Bytes>>
+where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+{
+    stream::unfold(Some(stream), move |stream| async move {
+        let mut stream = stream?;
+        match tokio::time::timeout(read_timeout, stream.next()).await {
+            Ok(Some(item)) => Some((item, Some(stream))),
+            Ok(None) => None,
+            Err(_) => Some((
+                Err(Error::message(
+                    ErrorKind::Io,
+                    "timed out reading the `hyper` response",
+                )),
+                None,
+            )),
+        }
+    })
+}
+
+/// When the request body finished sending, if it has.
+type SentAt = Arc<Mutex<Option<Instant>>>;
+
+/// Records when the request `body` has been sent.
+struct SentBody {
+    body: HyperBody,
+    sent: SentAt,
+}
+
+impl SentBody {
+    fn new(body: HyperBody, sent: SentAt) -> Self {
+        let body = Self { body, sent };
+        if body.body.is_end_stream() {
+            body.set_sent();
+        }
+        body
+    }
+
+    fn set_sent(&self) {
+        self.sent
+            .lock()
+            .expect("lock poisoned")
+            .get_or_insert_with(Instant::now);
+    }
+}
+
+impl http_body::Body for SentBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>>>> {
+        let frame = Pin::new(&mut self.body).poll_frame(cx);
+        if matches!(frame, Poll::Ready(None)) || (frame.is_ready() && self.body.is_end_stream()) {
+            self.set_sent();
+        }
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+/// Sends bytes as a request body in chunks, so that `hyper` only takes more once it has written the previous chunks.
+struct BytesBody(Bytes);
+
+impl http_body::Body for BytesBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>>>> {
+        if self.0.is_empty() {
+            return Poll::Ready(None);
+        }
+        let len = self.0.len().min(REQUEST_CHUNK_SIZE);
+        Poll::Ready(Some(Ok(Frame::data(self.0.split_to(len)))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.0.len() as u64)
+    }
+}
+
+/// Streams a [`SeekableStream`] as a request body of a known length.
+struct SeekableStreamBody {
+    stream: Box<dyn SeekableStream>,
+    remaining: u64,
+}
+
+impl http_body::Body for SeekableStreamBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>>>> {
+        let chunk = Pin::new(&mut *self.stream).poll_next(cx);
+        if let Poll::Ready(Some(Ok(bytes))) = &chunk {
+            self.remaining = self.remaining.saturating_sub(bytes.len() as u64);
+        }
+        chunk.map_ok(Frame::data)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
+
+/// Gets the `Proxy-Authorization` header value for the credentials in the `proxy` URL, if any.
+fn proxy_authorization(proxy: &Url) -> Option<String> {
+    if proxy.username().is_empty() {
+        return None;
+    }
+    let decode = |s: &str| {
+        percent_encoding::percent_decode_str(s)
+            .decode_utf8_lossy()
+            .into_owned()
+    };
+    let credentials = format!(
+        "{}:{}",
+        decode(proxy.username()),
+        decode(proxy.password().unwrap_or_default())
+    );
+    Some(format!("Basic {}", base64::encode(credentials)))
+}
+
+/// Connects to servers directly or through a proxy.
+#[derive(Clone)]
+struct ProxyConnector {
+    http: HttpConnector,
+    proxy: Arc<ProxyOptions>,
+}
+
+impl Service<::http::Uri> for ProxyConnector {
+    type Response = ProxyStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<ProxyStream, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), BoxError>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: ::http::Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        let proxy = dst
+            .to_string()
+            .parse::<Url>()
+            .ok()
+            .and_then(|url| self.proxy.proxy_for(&url).cloned());
+
+        Box::pin(async move {
+            let Some(proxy) = proxy else {
+                let io = http.call(dst).await?;
+                return Ok(ProxyStream { io, proxied: false });
+            };
+
+            let proxy_uri = format!(
+                "http://{}:{}",
+                proxy.host_str().unwrap_or_default(),
+                proxy.port_or_known_default().unwrap_or(80)
+            )
+            .parse()?;
+            let io = http.call(proxy_uri).await?;
+            if dst.scheme_str() == Some("https") {
+                let io = tunnel(io, &dst, &proxy).await?;
+                Ok(ProxyStream { io, proxied: false })
+            } else {
+                Ok(ProxyStream { io, proxied: true })
+            }
+        })
+    }
+}
+
+/// Opens a tunnel to `dst` through the proxy connected to by `io`.
+async fn tunnel(
+    io: TokioIo<TcpStream>,
+    dst: &::http::Uri,
+    proxy: &Url,
+) -> std::result::Result<TokioIo<TcpStream>, BoxError> {
+    let mut tcp = io.into_inner();
+    let host = dst.host().ok_or("missing host")?;
+    let port = dst.port_u16().unwrap_or(443);
+
+    let mut connect = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some(authorization) = proxy_authorization(proxy) {
+        connect.push_str(&format!(
+            "{}: {authorization}\r\n",
+            PROXY_AUTHORIZATION.as_str()
+        ));
+    }
+    connect.push_str("\r\n");
+    tcp.write_all(connect.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_CONNECT_RESPONSE_SIZE {
+            return Err("proxy response to CONNECT is too large".into());
+        }
+        let mut byte = [0u8; 1];
+        if tcp.read(&mut byte).await? == 0 {
+            return Err("proxy closed the connection during CONNECT".into());
+        }
+        response.push(byte[0]);
+    }
+
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(format!("proxy refused to CONNECT: {status_line}").into());
+    }
+    Ok(TokioIo::new(tcp))
+}
+
+/// A connection to a server or proxy.
+struct ProxyStream {
+    io: TokioIo<TcpStream>,
+    /// Whether requests are sent to an HTTP proxy rather than tunneled to the server.
+    proxied: bool,
+}
+
+impl Connection for ProxyStream {
+    fn connected(&self) -> Connected {
+        self.io.connected().proxy(self.proxied)
+    }
+}
+
+impl Read for ProxyStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_read(cx, buf)
+    }
+}
+
+impl Write for ProxyStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write_vectored(cx, bufs)
+    }
+}
+
+fn to_headers(map: &::http::HeaderMap) -> Headers {
+    let map = map
+        .iter()
+        .filter_map(|(k, v)| {
+            let key = k.as_str();
+            if let Ok(value) = v.to_str() {
+                Some((
+                    HeaderName::from(key.to_owned()),
+                    HeaderValue::from(value.to_owned()),
+                ))
+            } else {
+                warn!("header value for `{key}` is not utf8");
+                None
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    Headers::from(map)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{http::Method, stream::BytesStream};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    /// Reads a request from `stream`, returning its body.
+    async fn read_request(stream: &mut TcpStream) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let header_end = loop {
+            let mut chunk = [0u8; 1024];
+            let read = stream.read(&mut chunk).await.unwrap();
+            assert_ne!(0, read, "connection closed");
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break end + 4;
+            }
+        };
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_lowercase();
+        let content_length: usize = head
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .map_or(0, |value| value.trim().parse().unwrap());
+        while buffer.len() < header_end + content_length {
+            let mut chunk = [0u8; 1024];
+            let read = stream.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        buffer[header_end..].to_vec()
+    }
+
+    #[tokio::test]
+    async fn reuses_connection_and_streams_body() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        // Accept a single connection to serve both requests.
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut bodies = Vec::new();
+            for _ in 0..2 {
+                let body = read_request(&mut stream).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nx-test: value\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.write_all(&body).await.unwrap();
+                bodies.push(body);
+            }
+            bodies
+        });
+
+        let client = new_hyper_client();
+        let stream: Box<dyn SeekableStream> = Box::new(BytesStream::new("stream"));
+        for body in [Body::from("bytes"), Body::from(stream)] {
+            let mut request = Request::new(url.parse().unwrap(), Method::Post);
+            request.set_body(body);
+            let response = client.execute_request(&request).await.unwrap();
+            assert_eq!(StatusCode::Ok, response.status());
+            assert_eq!(
+                Some("value"),
+                response
+                    .headers()
+                    .get_optional_str(&HeaderName::from_static("x-test"))
+            );
+            response.into_raw_body().collect().await.unwrap();
+        }
+
+        assert_eq!(
+            vec![b"bytes".to_vec(), b"stream".to_vec()],
+            server.await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_http_requests_to_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = format!("http://user:p%40ss@{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0u8; 4096];
+            let read = stream.read(&mut buffer).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buffer[..read]).to_string()
+        });
+
+        let options = HttpClientOptions::default()
+            .proxy(ProxyOptions::all(proxy.parse().unwrap()).no_proxy(vec!["localhost".into()]));
+        let client = new_hyper_client_with_options(&options).unwrap();
+        let request = Request::new("http://contoso.com/path".parse().unwrap(), Method::Get);
+        let response = client.execute_request(&request).await.unwrap();
+        assert_eq!(StatusCode::NoContent, response.status());
+
+        let head = server.await.unwrap();
+        assert!(head.starts_with("GET http://contoso.com/path HTTP/1.1\r\n"));
+        // cspell:ignore dxnlcjpwqhnz
+        // "user:p@ss" encoded as base64.
+        assert!(head
+            .to_lowercase()
+            .contains("proxy-authorization: basic dxnlcjpwqhnz\r\n"));
+    }
+
+    #[tokio::test]
+    async fn read_timeout_excludes_sending_body() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        // Stop reading the request long enough for sending its body to outlast the read timeout.
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let body = read_request(&mut stream).await;
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .await
+                .unwrap();
+            body.len()
+        });
+
+        let options = HttpClientOptions::default().read_timeout(Duration::from_millis(100));
+        let client = new_hyper_client_with_options(&options).unwrap();
+        let mut request = Request::new(url.parse().unwrap(), Method::Post);
+        request.set_body(vec![0u8; 32 * 1024 * 1024]);
+        let response = client.execute_request(&request).await.unwrap();
+        assert_eq!(StatusCode::NoContent, response.status());
+        assert_eq!(32 * 1024 * 1024, server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn read_timeout_fails_waiting_for_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_request(&mut stream).await;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let options = HttpClientOptions::default().read_timeout(Duration::from_millis(100));
+        let client = new_hyper_client_with_options(&options).unwrap();
+        let mut request = Request::new(url.parse().unwrap(), Method::Post);
+        request.set_body("body");
+        let error = client.execute_request(&request).await.unwrap_err();
+        assert_eq!(&ErrorKind::Io, error.kind());
+        server.abort();
+    }
+
+    #[test]
+    fn rejects_unsupported_proxy_scheme() {
+        let options = HttpClientOptions::default().proxy(ProxyOptions::all(
+            "socks5://localhost:1080".parse().unwrap(),
+        ));
+        assert!(new_hyper_client_with_options(&options).is_err());
+    }
+}
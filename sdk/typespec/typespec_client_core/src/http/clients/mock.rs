@@ -0,0 +1,338 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    error::{Error, ErrorKind, Result},
+    http::{
+        headers::{HeaderName, HeaderValue, Headers, CONTENT_TYPE},
+        Body, HttpClient, Method, Request, Response, StatusCode,
+    },
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::TryStreamExt;
+use serde::Serialize;
+use std::{collections::VecDeque, sync::Mutex};
+
+/// A request expected by a [`MockHttpClient`].
+///
+/// A request matches if it has the expected method and path, and any expected query parameters, headers, and JSON body.
+/// Other query parameters and headers are ignored.
+#[derive(Clone, Debug)]
+pub struct ExpectedRequest {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    json_body: Option<serde_json::Value>,
+}
+
+impl ExpectedRequest {
+    /// Expects a request with `method` to the URL `path`, e.g. `/secrets/name`.
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            json_body: None,
+        }
+    }
+
+    /// Expects a query parameter `name` with `value`.
+    #[must_use]
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Expects a header `name` with `value`.
+    #[must_use]
+    pub fn header(mut self, name: impl Into<HeaderName>, value: impl Into<HeaderValue>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Expects a JSON body equal to `body`, regardless of formatting and the order of object members.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` cannot be serialized to JSON.
+    #[must_use]
+    pub fn json_body<T: Serialize>(mut self, body: &T) -> Self {
+        self.json_body = Some(serde_json::to_value(body).expect("expected body is not valid JSON"));
+        self
+    }
+
+    /// Describes how `request` does not match, if it does not.
+    fn mismatch(&self, request: &Request, body: &[u8]) -> Option<String> {
+        if request.method() != &self.method || request.url().path() != self.path {
+            return Some(format!("expected {} {}", self.method, self.path));
+        }
+        for (name, value) in &self.query {
+            if !request
+                .url()
+                .query_pairs()
+                .any(|(n, v)| n == name.as_str() && v == value.as_str())
+            {
+                return Some(format!("expected query parameter {name}={value}"));
+            }
+        }
+        for (name, value) in &self.headers {
+            if request.headers().get_optional_str(name) != Some(value.as_str()) {
+                return Some(format!(
+                    "expected header {}: {}",
+                    name.as_str(),
+                    value.as_str()
+                ));
+            }
+        }
+        if let Some(expected) = &self.json_body {
+            if serde_json::from_slice::<serde_json::Value>(body)
+                .ok()
+                .as_ref()
+                != Some(expected)
+            {
+                return Some(format!("expected JSON body {expected}"));
+            }
+        }
+        None
+    }
+}
+
+/// A response returned by a [`MockHttpClient`].
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: Headers,
+    body: Bytes,
+}
+
+impl MockResponse {
+    /// Creates a response with `status` and no headers or body.
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: Headers::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// Adds a header `name` with `value`.
+    #[must_use]
+    pub fn header(mut self, name: impl Into<HeaderName>, value: impl Into<HeaderValue>) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets the body.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Sets the body to `body` serialized as JSON and the `content-type` header to `application/json`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` cannot be serialized to JSON.
+    #[must_use]
+    pub fn json_body<T: Serialize>(self, body: &T) -> Self {
+        let body = serde_json::to_vec(body).expect("response body is not valid JSON");
+        self.header(CONTENT_TYPE, "application/json").body(body)
+    }
+}
+
+/// An [`HttpClient`] that checks requests against expectations and returns canned responses.
+///
+/// Each request must match the next [`ExpectedRequest`] in the order they were added, and gets its [`MockResponse`].
+/// A request that does not match returns an error. Call [`MockHttpClient::assert_expectations_met`] at the end of a test
+/// to check that every expected request was sent and no unexpected requests were sent.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use typespec_client_core::http::{
+/// #     ClientOptions, Context, ExpectedRequest, Method, MockHttpClient, MockResponse, Pipeline, Request, StatusCode,
+/// #     TransportOptions,
+/// # };
+/// # #[tokio::main]
+/// # async fn main() -> typespec_client_core::Result<()> {
+/// let client = Arc::new(MockHttpClient::new());
+/// client.expect(
+///     ExpectedRequest::new(Method::Get, "/secrets/name").query("api-version", "7.5"),
+///     MockResponse::new(StatusCode::Ok).json_body(&serde_json::json!({ "value": "secret" })),
+/// );
+/// let options = ClientOptions {
+///     transport: Some(TransportOptions::new(client.clone())),
+///     ..Default::default()
+/// };
+///
+/// // Typically, the `options` are passed to the client under test.
+/// let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+/// let mut request = Request::new("https://vault/secrets/name?api-version=7.5".parse()?, Method::Get);
+/// let response = pipeline.send::<()>(&Context::new(), &mut request).await?;
+/// assert_eq!(StatusCode::Ok, response.status());
+///
+/// client.assert_expectations_met();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockHttpClient {
+    expectations: Mutex<VecDeque<(ExpectedRequest, MockResponse)>>,
+    failures: Mutex<Vec<String>>,
+}
+
+impl MockHttpClient {
+    /// Creates a `MockHttpClient` that expects no requests.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects a request matching `request` after any previously expected requests, and returns `response` for it.
+    pub fn expect(&self, request: ExpectedRequest, response: MockResponse) {
+        self.expectations
+            .lock()
+            .expect("lock poisoned")
+            .push_back((request, response));
+    }
+
+    /// Asserts that every expected request was sent and no unexpected requests were sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a description of each unexpected request and each expected request that was not sent.
+    #[track_caller]
+    pub fn assert_expectations_met(&self) {
+        let mut problems = self.failures.lock().expect("lock poisoned").clone();
+        problems.extend(self.expectations.lock().expect("lock poisoned").iter().map(
+            |(expected, _)| {
+                format!(
+                    "expected {} {} was not sent",
+                    expected.method, expected.path
+                )
+            },
+        ));
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+    }
+
+    fn fail(&self, message: String) -> Error {
+        self.failures
+            .lock()
+            .expect("lock poisoned")
+            .push(message.clone());
+        Error::message(ErrorKind::Other, message)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl HttpClient for MockHttpClient {
+    async fn execute_request(&self, request: &Request) -> Result<Response> {
+        let body = match request.body() {
+            Body::Bytes(bytes) => bytes.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Body::SeekableStream(stream) => {
+                let chunks: Vec<Bytes> = stream.clone().try_collect().await?;
+                chunks.concat().into()
+            }
+        };
+
+        let next = self.expectations.lock().expect("lock poisoned").pop_front();
+        let Some((expected, response)) = next else {
+            return Err(self.fail(format!(
+                "unexpected request {} {}",
+                request.method(),
+                request.url()
+            )));
+        };
+        if let Some(mismatch) = expected.mismatch(request, &body) {
+            return Err(self.fail(format!(
+                "request {} {} did not match: {mismatch}",
+                request.method(),
+                request.url()
+            )));
+        }
+        Ok(Response::from_bytes(
+            response.status,
+            response.headers,
+            response.body,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(method: Method, url: &str, body: serde_json::Value) -> Request {
+        let mut request = Request::new(url.parse().unwrap(), method);
+        request.insert_header("x-ms-client-request-id", "1");
+        request.set_json(&body).unwrap();
+        request
+    }
+
+    #[tokio::test]
+    async fn returns_responses_for_expected_requests() {
+        let client = MockHttpClient::new();
+        client.expect(
+            ExpectedRequest::new(Method::Put, "/secrets/a")
+                .query("api-version", "1.0")
+                .header("x-ms-client-request-id", "1")
+                .json_body(&json!({ "value": "secret", "tags": { "a": "b" } })),
+            MockResponse::new(StatusCode::Ok).json_body(&json!({ "id": "a" })),
+        );
+        client.expect(
+            ExpectedRequest::new(Method::Get, "/secrets/b"),
+            MockResponse::new(StatusCode::NotFound),
+        );
+
+        let response = client
+            .execute_request(&request(
+                Method::Put,
+                "https://vault/secrets/a?api-version=1.0&other=1",
+                json!({ "tags": { "a": "b" }, "value": "secret" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::Ok, response.status());
+        let body = response.into_raw_body().collect().await.unwrap();
+        assert_eq!(r#"{"id":"a"}"#, body);
+
+        let response = client
+            .execute_request(&Request::new(
+                "https://vault/secrets/b".parse().unwrap(),
+                Method::Get,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::NotFound, response.status());
+        client.assert_expectations_met();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "did not match: expected JSON body")]
+    async fn reports_mismatched_requests() {
+        let client = MockHttpClient::new();
+        client.expect(
+            ExpectedRequest::new(Method::Put, "/secrets/a").json_body(&json!({ "value": "a" })),
+            MockResponse::new(StatusCode::Ok),
+        );
+
+        let err = client
+            .execute_request(&request(
+                Method::Put,
+                "https://vault/secrets/a",
+                json!({ "value": "b" }),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(ErrorKind::Other, *err.kind());
+        client.assert_expectations_met();
+    }
+}
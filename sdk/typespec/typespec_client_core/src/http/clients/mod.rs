@@ -3,33 +3,91 @@
 
 //! Built-in HTTP clients.
 
-#[cfg(not(any(feature = "reqwest", feature = "reqwest_rustls")))]
+#[cfg(all(feature = "hyper", not(target_arch = "wasm32")))]
+mod hyper;
+#[cfg(feature = "test")]
+mod mock;
+#[cfg(not(any(
+    feature = "reqwest",
+    feature = "reqwest_rustls",
+    all(feature = "hyper", not(target_arch = "wasm32"))
+)))]
 mod noop;
 #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
 mod reqwest;
 
-#[cfg(not(any(feature = "reqwest", feature = "reqwest_rustls")))]
+#[cfg(all(feature = "hyper", not(target_arch = "wasm32")))]
+pub use self::hyper::{new_hyper_client, new_hyper_client_with_options};
+#[cfg(feature = "test")]
+pub use self::mock::{ExpectedRequest, MockHttpClient, MockResponse};
+#[cfg(not(any(
+    feature = "reqwest",
+    feature = "reqwest_rustls",
+    all(feature = "hyper", not(target_arch = "wasm32"))
+)))]
 use self::noop::new_noop_client;
 #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
-use self::reqwest::new_reqwest_client;
+use self::reqwest::{new_reqwest_client, new_reqwest_client_with_options};
 
-use crate::http::{Request, Response};
+use crate::http::{HttpClientOptions, Request, Response};
 use async_trait::async_trait;
 use std::sync::Arc;
 use typespec::error::Result;
 
 /// Create a new [`HttpClient`].
+///
+/// The `reqwest` backend is used if enabled; otherwise, the `hyper` backend is used if enabled.
 pub fn new_http_client() -> Arc<dyn HttpClient> {
     #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
     {
         new_reqwest_client()
     }
-    #[cfg(not(any(feature = "reqwest", feature = "reqwest_rustls")))]
+    #[cfg(all(
+        not(any(feature = "reqwest", feature = "reqwest_rustls")),
+        feature = "hyper",
+        not(target_arch = "wasm32")
+    ))]
+    {
+        new_hyper_client()
+    }
+    #[cfg(not(any(
+        feature = "reqwest",
+        feature = "reqwest_rustls",
+        all(feature = "hyper", not(target_arch = "wasm32"))
+    )))]
     {
         new_noop_client()
     }
 }
 
+/// Create a new [`HttpClient`] configured with the given options.
+///
+/// The `reqwest` backend is used if enabled; otherwise, the `hyper` backend is used if enabled.
+/// Returns an error if the options are not valid, e.g. a certificate cannot be parsed.
+pub fn new_http_client_with_options(options: &HttpClientOptions) -> Result<Arc<dyn HttpClient>> {
+    #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+    {
+        new_reqwest_client_with_options(options)
+    }
+    #[cfg(all(
+        not(any(feature = "reqwest", feature = "reqwest_rustls")),
+        feature = "hyper",
+        not(target_arch = "wasm32")
+    ))]
+    {
+        new_hyper_client_with_options(options)
+    }
+    #[cfg(not(any(
+        feature = "reqwest",
+        feature = "reqwest_rustls",
+        all(feature = "hyper", not(target_arch = "wasm32"))
+    )))]
+    {
+        let _ = options;
+        Ok(new_noop_client())
+    }
+}
+
 /// An HTTP client which can send requests.
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
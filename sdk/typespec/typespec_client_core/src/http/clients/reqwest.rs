@@ -5,7 +5,7 @@ use crate::http::{
     headers::{HeaderName, HeaderValue, Headers},
     request::{Body, Request},
     response::PinnedStream,
-    HttpClient, Method, Response, StatusCode,
+    HttpClient, HttpClientOptions, Method, Response, StatusCode,
 };
 use async_trait::async_trait;
 use futures::TryStreamExt;
@@ -15,25 +15,98 @@ use typespec::error::{Error, ErrorKind, Result, ResultExt};
 
 /// Create a new [`HttpClient`] with the `reqwest` backend.
 pub fn new_reqwest_client() -> Arc<dyn HttpClient> {
+    new_reqwest_client_with_options(&HttpClientOptions::default())
+        .expect("failed to build `reqwest` client")
+}
+
+/// Create a new [`HttpClient`] with the `reqwest` backend configured with the given options.
+///
+/// If no proxy is set in the `options`, `reqwest` uses the system proxy configuration including the `HTTPS_PROXY` environment variable.
+pub fn new_reqwest_client_with_options(options: &HttpClientOptions) -> Result<Arc<dyn HttpClient>> {
     debug!("instantiating an http client using the reqwest backend");
 
-    // Set `pool_max_idle_per_host` to `0` to avoid an issue in the underlying
-    // `hyper` library that causes the `reqwest` client to hang in some cases.
-    //
-    // See <https://github.com/hyperium/hyper/issues/2312> for more details.
     #[cfg(not(target_arch = "wasm32"))]
-    let client = ::reqwest::ClientBuilder::new()
-        .pool_max_idle_per_host(0)
-        .build()
-        .expect("failed to build `reqwest` client");
+    let builder = configure(::reqwest::ClientBuilder::new(), options)?;
 
-    // `reqwest` does not implement `pool_max_idle_per_host()` on WASM.
+    // `reqwest` does not implement most transport options on WASM.
     #[cfg(target_arch = "wasm32")]
-    let client = ::reqwest::ClientBuilder::new()
+    let builder = {
+        let _ = options;
+        ::reqwest::ClientBuilder::new()
+    };
+
+    let client = builder
         .build()
-        .expect("failed to build `reqwest` client");
+        .context(ErrorKind::Other, "failed to build `reqwest` client")?;
+    Ok(Arc::new(client))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn configure(
+    mut builder: ::reqwest::ClientBuilder,
+    options: &HttpClientOptions,
+) -> Result<::reqwest::ClientBuilder> {
+    // By default, set `pool_max_idle_per_host` to `0` to avoid an issue in the underlying
+    // `hyper` library that causes the `reqwest` client to hang in some cases.
+    //
+    // See <https://github.com/hyperium/hyper/issues/2312> for more details.
+    builder = builder.pool_max_idle_per_host(options.pool_max_idle_per_host.unwrap_or(0));
+    if let Some(pool_idle_timeout) = options.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(pool_idle_timeout);
+    }
+
+    if let Some(proxy) = options.proxy.clone() {
+        builder = builder.no_proxy();
+        if !proxy.is_empty() {
+            builder = builder.proxy(::reqwest::Proxy::custom(move |url| {
+                proxy.proxy_for(url).cloned()
+            }));
+        }
+    }
+
+    if let Some(connect_timeout) = options.connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    if let Some(read_timeout) = options.read_timeout {
+        builder = builder.read_timeout(read_timeout);
+    }
+
+    for pem in &options.root_certificates {
+        let certificates = ::reqwest::Certificate::from_pem_bundle(pem).context(
+            ErrorKind::DataConversion,
+            "failed to parse root certificates",
+        )?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some(client_certificate) = &options.client_certificate {
+        #[cfg(feature = "reqwest")]
+        let identity = ::reqwest::Identity::from_pkcs8_pem(
+            &client_certificate.certificate_chain,
+            &client_certificate.private_key,
+        );
+        #[cfg(not(feature = "reqwest"))]
+        let identity = ::reqwest::Identity::from_pem(
+            &[
+                client_certificate.private_key.as_slice(),
+                &client_certificate.certificate_chain,
+            ]
+            .concat(),
+        );
+        let identity = identity.context(
+            ErrorKind::DataConversion,
+            "failed to parse client certificate",
+        )?;
+        builder = builder.identity(identity);
+    }
+
+    if !options.prefer_http2 {
+        builder = builder.http1_only();
+    }
 
-    Arc::new(client)
+    Ok(builder)
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -60,7 +133,7 @@ impl HttpClient for ::reqwest::Client {
         }
         .context(ErrorKind::Other, "failed to build `reqwest` request")?;
 
-        debug!("performing request {method} '{url}' with `reqwest`");
+        debug!("performing request {method} with `reqwest`");
         let rsp = self
             .execute(reqwest_request)
             .await
@@ -31,8 +31,11 @@ pub const LINK: HeaderName = HeaderName::from_static("link");
 pub const LOCATION: HeaderName = HeaderName::from_static("location");
 pub const OPERATION_LOCATION: HeaderName = HeaderName::from_static("operation-location");
 pub const PREFER: HeaderName = HeaderName::from_static("prefer");
+pub const PROXY_AUTHORIZATION: HeaderName = HeaderName::from_static("proxy-authorization");
 pub const RANGE: HeaderName = HeaderName::from_static("range");
 pub const RETRY_AFTER: HeaderName = HeaderName::from_static("retry-after");
 pub const SERVER: HeaderName = HeaderName::from_static("server");
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+pub const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
 pub const USER_AGENT: HeaderName = HeaderName::from_static("user-agent");
 pub const WWW_AUTHENTICATE: HeaderName = HeaderName::from_static("www-authenticate");
@@ -3,6 +3,9 @@
 
 use super::*;
 
+pub const CLIENT_REQUEST_ID: HeaderName = HeaderName::from_static("x-ms-client-request-id");
 pub const ERROR_CODE: HeaderName = HeaderName::from_static("x-ms-error-code");
+pub const MS_RANGE: HeaderName = HeaderName::from_static("x-ms-range");
+pub const REQUEST_ID: HeaderName = HeaderName::from_static("x-ms-request-id");
 pub const RETRY_AFTER_MS: HeaderName = HeaderName::from_static("retry-after-ms");
 pub const X_MS_RETRY_AFTER_MS: HeaderName = HeaderName::from_static("x-ms-retry-after-ms");
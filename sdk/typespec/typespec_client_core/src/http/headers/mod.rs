@@ -197,6 +197,11 @@ impl Headers {
         self.0.insert(key.into(), value.into());
     }
 
+    /// Remove a header, returning its value if it was present.
+    pub fn remove(&mut self, key: &HeaderName) -> Option<HeaderValue> {
+        self.0.remove(key)
+    }
+
     /// Add headers to the headers collection.
     ///
     /// ## Errors
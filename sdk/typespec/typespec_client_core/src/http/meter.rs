@@ -0,0 +1,66 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Abstractions for HTTP metrics.
+//!
+//! Implement [`Meter`] to export metrics recorded by the [`MetricsPolicy`](crate::http::policies::MetricsPolicy)
+//! to a metrics system, or enable the `metrics` feature to use the [`MetricsMeter`] for the `metrics` crate.
+
+use std::fmt::Debug;
+
+/// The number of calls, counted once for all tries of each call.
+pub const HTTP_CLIENT_REQUESTS: &str = "http.client.requests";
+
+/// The duration of calls in seconds, including all tries and the delays between them.
+///
+/// This is not recorded on `wasm32` targets, which have no monotonic clock.
+pub const HTTP_CLIENT_REQUEST_DURATION: &str = "http.client.request.duration";
+
+/// The number of tries after the first try of each call.
+pub const HTTP_CLIENT_RETRIES: &str = "http.client.retries";
+
+/// The size of response bodies in bytes, from their `content-length` header.
+pub const HTTP_CLIENT_RESPONSE_BODY_SIZE: &str = "http.client.response.body.size";
+
+/// The number of calls that returned an error, including error responses.
+pub const HTTP_CLIENT_ERRORS: &str = "http.client.errors";
+
+/// The label for the name of the client library, e.g. its crate name.
+pub const CLIENT_LABEL: &str = "client";
+
+/// The label for the [`OperationName`](crate::http::OperationName), or the HTTP method if not set.
+pub const OPERATION_LABEL: &str = "operation";
+
+/// The label for the host the request was sent to.
+pub const HOST_LABEL: &str = "host";
+
+/// The label for the response status code, or `none` if no response was received.
+pub const STATUS_LABEL: &str = "status";
+
+/// Labels recorded with a metric, as pairs of label names and values.
+pub type MetricLabels<'a> = &'a [(&'static str, String)];
+
+/// Records metrics.
+pub trait Meter: Send + Sync + Debug {
+    /// Adds `value` to the counter `name`.
+    fn add_to_counter(&self, name: &'static str, value: u64, labels: MetricLabels<'_>);
+
+    /// Records `value` in the histogram `name`.
+    fn record_histogram(&self, name: &'static str, value: f64, labels: MetricLabels<'_>);
+}
+
+/// A [`Meter`] that records metrics using the global recorder of the `metrics` crate.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsMeter;
+
+#[cfg(feature = "metrics")]
+impl Meter for MetricsMeter {
+    fn add_to_counter(&self, name: &'static str, value: u64, labels: MetricLabels<'_>) {
+        metrics::counter!(name, labels).increment(value);
+    }
+
+    fn record_histogram(&self, name: &'static str, value: f64, labels: MetricLabels<'_>) {
+        metrics::histogram!(name, labels).record(value);
+    }
+}
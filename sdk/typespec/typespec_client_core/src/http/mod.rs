@@ -3,26 +3,38 @@
 
 //! Types and functions for building HTTP clients.
 
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+mod cancellation;
 mod clients;
 mod context;
 pub mod headers;
+mod meter;
 mod models;
+mod operation;
 mod options;
 mod pager;
 mod pipeline;
 pub mod policies;
 pub mod request;
 pub mod response;
+#[cfg(feature = "distributed_tracing")]
+mod tracer;
 
+pub use cancellation::*;
 pub use clients::*;
 pub use context::*;
 pub use headers::Header;
+pub use meter::*;
 pub use models::*;
+pub use operation::*;
 pub use options::*;
 pub use pager::*;
 pub use pipeline::*;
 pub use request::{Body, Request, RequestContent};
 pub use response::{Model, Response};
+#[cfg(feature = "distributed_tracing")]
+pub use tracer::*;
 
 // Re-export important types.
 pub use http_types::{Method, StatusCode};
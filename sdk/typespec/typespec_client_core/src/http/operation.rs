@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::borrow::Cow;
+
+/// The name of the logical operation, e.g. a client method, used to name its span and label its metrics.
+///
+/// Add this to the [`Context`](crate::http::Context) passed to the pipeline. If not set, the HTTP method is used.
+#[derive(Clone, Debug)]
+pub struct OperationName(Cow<'static, str>);
+
+impl OperationName {
+    /// Creates a new `OperationName`.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// Gets the name.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for OperationName {
+    fn from(name: &'static str) -> Self {
+        Self::new(name)
+    }
+}
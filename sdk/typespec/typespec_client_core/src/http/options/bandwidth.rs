@@ -0,0 +1,39 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::stream::BandwidthLimiter;
+
+/// Options for limiting the bandwidth used by request and response bodies.
+///
+/// The limits are shared by all requests made by the client. Clone a [`BandwidthLimiter`] into the options of several
+/// clients to share a limit between them.
+///
+/// # Example
+///
+/// Limiting uploads to 1 MiB per second.
+/// ```
+/// # use typespec_client_core::{http::{BandwidthOptions, ClientOptions}, stream::BandwidthLimiter};
+/// let options = ClientOptions {
+///     bandwidth: Some(BandwidthOptions::default().upload(BandwidthLimiter::new(1024 * 1024))),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BandwidthOptions {
+    /// Limits the bytes per second sent by [`Body::SeekableStream`](crate::http::Body::SeekableStream) request bodies.
+    ///
+    /// The default is `None`.
+    pub upload: Option<BandwidthLimiter>,
+
+    /// Limits the bytes per second received by response bodies.
+    ///
+    /// The default is `None`.
+    pub download: Option<BandwidthLimiter>,
+}
+
+impl BandwidthOptions {
+    setters! {
+        upload: BandwidthLimiter => Some(upload),
+        download: BandwidthLimiter => Some(download),
+    }
+}
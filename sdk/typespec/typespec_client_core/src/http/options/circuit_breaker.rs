@@ -0,0 +1,54 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::time::Duration;
+
+/// Options for how a circuit breaker should behave.
+///
+/// A circuit breaker counts consecutive failed requests to each host. After `failure_threshold` failures
+/// the circuit opens and requests to that host fail immediately until `cool_down` has elapsed.
+/// A single request is then allowed through: if it succeeds the circuit closes; otherwise, it opens again.
+///
+/// # Example
+///
+/// Opening the circuit after 3 consecutive failures for 10 seconds.
+/// ```
+/// # use core::time::Duration;
+/// # use typespec_client_core::http::{CircuitBreakerOptions, ClientOptions};
+/// let options = ClientOptions {
+///     circuit_breaker: Some(
+///         CircuitBreakerOptions::default()
+///             .failure_threshold(3u32)
+///             .cool_down(Duration::from_secs(10)),
+///     ),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerOptions {
+    /// The number of consecutive failed requests to a host after which the circuit opens.
+    ///
+    /// The default is 5.
+    pub failure_threshold: u32,
+
+    /// The time the circuit stays open before a request is allowed through to test the host.
+    ///
+    /// The default is 30 seconds.
+    pub cool_down: Duration,
+}
+
+impl CircuitBreakerOptions {
+    setters! {
+        failure_threshold: u32 => failure_threshold,
+        cool_down: Duration => cool_down,
+    }
+}
+
+impl Default for CircuitBreakerOptions {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cool_down: Duration::from_secs(30),
+        }
+    }
+}
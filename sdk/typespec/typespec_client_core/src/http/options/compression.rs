@@ -0,0 +1,58 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+/// Options to compress request bodies.
+///
+/// Only request bodies of a known size are compressed. A request that already has a `content-encoding` header
+/// is sent as is.
+#[derive(Clone, Debug)]
+pub struct CompressionOptions {
+    /// The encoding to compress request bodies with.
+    ///
+    /// The default is [`CompressionEncoding::Gzip`].
+    pub encoding: CompressionEncoding,
+
+    /// The minimum size in bytes of a request body to compress.
+    ///
+    /// The default is 1024 bytes.
+    pub threshold: usize,
+}
+
+impl CompressionOptions {
+    setters! {
+        #[doc = "Set the encoding to compress request bodies with."]
+        encoding: CompressionEncoding => encoding,
+        #[doc = "Set the minimum size in bytes of a request body to compress."]
+        threshold: usize => threshold,
+    }
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        Self {
+            encoding: CompressionEncoding::default(),
+            threshold: 1024,
+        }
+    }
+}
+
+/// The encoding to compress request bodies with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompressionEncoding {
+    /// The `gzip` encoding.
+    #[default]
+    Gzip,
+
+    /// The `deflate` encoding, which is zlib-wrapped deflate as defined by HTTP.
+    Deflate,
+}
+
+impl CompressionEncoding {
+    /// Gets the value of the `content-encoding` header for this encoding.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+}
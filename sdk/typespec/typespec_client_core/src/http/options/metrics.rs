@@ -0,0 +1,47 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::Meter;
+use std::{borrow::Cow, sync::Arc};
+
+/// Options for recording HTTP metrics.
+///
+/// Metrics are labeled with the name of the client library, the [`OperationName`](crate::http::OperationName)
+/// in the [`Context`](crate::http::Context), the host, and the response status code.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use typespec_client_core::http::{ClientOptions, Meter, MetricsOptions};
+/// # fn doc(meter: Arc<dyn Meter>) {
+/// let options = ClientOptions {
+///     metrics: Some(MetricsOptions::new(meter)),
+///     ..Default::default()
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MetricsOptions {
+    /// The [`Meter`] to record metrics with.
+    pub meter: Arc<dyn Meter>,
+
+    /// The name of the client library to label metrics with, e.g. its crate name.
+    ///
+    /// The default is `None`, in which case clients may set their own name.
+    pub client_name: Option<Cow<'static, str>>,
+}
+
+impl MetricsOptions {
+    /// Creates `MetricsOptions` that record metrics with `meter`.
+    pub fn new(meter: Arc<dyn Meter>) -> Self {
+        Self {
+            meter,
+            client_name: None,
+        }
+    }
+
+    setters! {
+        client_name: Cow<'static, str> => Some(client_name),
+    }
+}
@@ -3,16 +3,41 @@
 
 //! Options and builders for clients, client methods, and policies.
 
+mod bandwidth;
 pub mod builders;
+mod circuit_breaker;
+#[cfg(feature = "gzip")]
+mod compression;
+mod http_client;
+mod logging;
+mod metrics;
+mod rate_limit;
+mod response_cache;
 mod retry;
 mod transport;
 
+pub use bandwidth::*;
+pub use circuit_breaker::*;
+#[cfg(feature = "gzip")]
+pub use compression::*;
+pub use http_client::*;
+pub use logging::*;
+pub use metrics::*;
+pub use rate_limit::*;
+pub use response_cache::*;
 pub use retry::*;
 pub use transport::*;
 
-use crate::http::{policies::Policy, Context};
+#[cfg(feature = "distributed_tracing")]
+use crate::http::Tracer;
+use crate::{
+    async_runtime::AsyncRuntime,
+    date::Clock,
+    http::{policies::Policy, Context},
+};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Client options allow customization of general client policies, retry options, and more.
 #[derive(Clone, Debug, Default)]
@@ -24,10 +49,70 @@ pub struct ClientOptions {
     pub per_try_policies: Vec<Arc<dyn Policy>>,
 
     /// Retry options.
+    ///
+    /// These can be overridden for a single method call by adding [`RetryOptions`] to the [`Context`].
     pub retry: Option<RetryOptions>,
 
+    /// The maximum time to wait for a single try to return a response.
+    ///
+    /// A try that does not complete in time is abandoned and retried according to the retry options.
+    /// If `None`, a try may wait indefinitely.
+    pub per_try_timeout: Option<Duration>,
+
+    /// Circuit breaker options.
+    ///
+    /// If `None`, requests are always sent regardless of previous failures.
+    pub circuit_breaker: Option<CircuitBreakerOptions>,
+
+    /// Rate limit options.
+    ///
+    /// If `None`, requests are sent as soon as possible.
+    pub rate_limit: Option<RateLimitOptions>,
+
+    /// Options to cache `GET` responses and revalidate them with their `ETag`.
+    ///
+    /// If `None`, responses are not cached.
+    pub response_cache: Option<ResponseCacheOptions>,
+
+    /// Options to compress request bodies.
+    ///
+    /// If `None`, request bodies are not compressed.
+    #[cfg(feature = "gzip")]
+    pub compression: Option<CompressionOptions>,
+
+    /// Bandwidth options.
+    ///
+    /// If `None`, request and response bodies are transferred as fast as possible.
+    pub bandwidth: Option<BandwidthOptions>,
+
+    /// Logging options.
+    pub logging: Option<LoggingOptions>,
+
     /// Transport options.
     pub transport: Option<TransportOptions>,
+
+    /// The [`AsyncRuntime`] to sleep and spawn background tasks on.
+    ///
+    /// This can be overridden for a single method call by adding an `Arc<dyn AsyncRuntime>` to the [`Context`].
+    /// If `None`, the [`default_runtime`](crate::async_runtime::default_runtime) is used.
+    pub runtime: Option<Arc<dyn AsyncRuntime>>,
+
+    /// The [`Clock`] used to expire retries and access tokens.
+    ///
+    /// This can be overridden for a single method call by adding an `Arc<dyn Clock>` to the [`Context`].
+    /// If `None`, the [`SystemClock`](crate::date::SystemClock) is used.
+    pub clock: Option<Arc<dyn Clock>>,
+
+    /// The [`Tracer`] used to create spans for each call and each try.
+    ///
+    /// If `None`, no spans are created.
+    #[cfg(feature = "distributed_tracing")]
+    pub tracer: Option<Arc<dyn Tracer>>,
+
+    /// Options to record metrics for each call.
+    ///
+    /// If `None`, no metrics are recorded.
+    pub metrics: Option<MetricsOptions>,
 }
 
 /// Method options allow customization of client method calls.
@@ -0,0 +1,60 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+/// Options for limiting the rate at which requests are sent.
+///
+/// Requests wait until they can be sent without exceeding the rate. Up to `burst` requests may be sent at once
+/// after a period of inactivity. When the service responds with `429 Too Many Requests`, the rate is halved
+/// and no further requests are sent until any `retry-after` period has elapsed; the rate then recovers
+/// gradually with each successful response.
+///
+/// # Example
+///
+/// Limiting requests to each host to 10 per second with bursts of up to 20 requests.
+/// ```
+/// # use typespec_client_core::http::{ClientOptions, RateLimitOptions};
+/// let options = ClientOptions {
+///     rate_limit: Some(
+///         RateLimitOptions::default()
+///             .requests_per_second(10.0)
+///             .burst(20u32)
+///             .per_host(true),
+///     ),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimitOptions {
+    /// The maximum sustained number of requests sent per second.
+    ///
+    /// If the rate is not positive, including NaN, requests are not limited. The default is 100.
+    pub requests_per_second: f64,
+
+    /// The maximum number of requests that may be sent at once.
+    ///
+    /// The default is 100.
+    pub burst: u32,
+
+    /// Whether to limit the rate of requests to each host separately, rather than all requests made by the client.
+    ///
+    /// The default is `false`.
+    pub per_host: bool,
+}
+
+impl RateLimitOptions {
+    setters! {
+        requests_per_second: f64 => requests_per_second,
+        burst: u32 => burst,
+        per_host: bool => per_host,
+    }
+}
+
+impl Default for RateLimitOptions {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 100.0,
+            burst: 100,
+            per_host: false,
+        }
+    }
+}
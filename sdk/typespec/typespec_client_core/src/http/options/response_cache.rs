@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::{
+    headers::{HeaderName, ACCEPT},
+    policies::{MemoryResponseCache, ResponseCache},
+};
+use std::sync::Arc;
+
+/// Options for caching `GET` responses and revalidating them with their `ETag`.
+///
+/// A `200 OK` response with an `ETag` header is stored in the cache. When the same resource is requested again,
+/// the request is sent with an `If-None-Match` header, and if the service responds with `304 Not Modified`,
+/// the stored response is returned instead. Responses with a `Cache-Control: no-store` header are not cached.
+///
+/// The headers of a `304 Not Modified` response, e.g. `Date` and `x-ms-request-id`, replace those of the stored response.
+///
+/// Requests that already have an `If-None-Match` header, and ranged requests with a `Range` or `x-ms-range` header,
+/// are sent unchanged.
+///
+/// Responses are cached before requests are authenticated, so the cache cannot tell apart the responses of clients
+/// using different credentials. Clients that share a [`store`](ResponseCacheOptions::store) but not a credential
+/// must each set a different [`partition`](ResponseCacheOptions::partition).
+///
+/// # Example
+///
+/// Caching up to 64 MiB of responses, each up to 4 MiB.
+/// ```
+/// # use std::sync::Arc;
+/// # use typespec_client_core::http::{
+/// #     policies::{MemoryResponseCache, ResponseCache},
+/// #     ClientOptions, ResponseCacheOptions,
+/// # };
+/// let store: Arc<dyn ResponseCache> = Arc::new(MemoryResponseCache::new(64 * 1024 * 1024));
+/// let options = ClientOptions {
+///     response_cache: Some(
+///         ResponseCacheOptions::default()
+///             .store(store)
+///             .max_body_size(4 * 1024 * 1024usize),
+///     ),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct ResponseCacheOptions {
+    /// Where responses are stored.
+    ///
+    /// Share a store between the options of several clients to share cached responses between them.
+    /// The default is a [`MemoryResponseCache`] of up to 16 MiB.
+    pub store: Arc<dyn ResponseCache>,
+
+    /// Identifies the responses cached by clients with the same credential, e.g. the name of the credential.
+    ///
+    /// Responses cached with one partition are never returned for requests made with another.
+    /// The default is `None`, which is a partition of its own.
+    pub partition: Option<String>,
+
+    /// The largest response body, in bytes, to cache.
+    ///
+    /// Responses without a `Content-Length` header are not cached.
+    /// The default is 1 MiB.
+    pub max_body_size: usize,
+
+    /// Request headers that, in addition to the URL, identify a cached response.
+    ///
+    /// Headers added by policies after the cache, e.g. `Authorization`, are never present.
+    /// Use a [`partition`](ResponseCacheOptions::partition) to separate the responses of different credentials.
+    /// The default is `Accept`.
+    pub vary: Vec<HeaderName>,
+}
+
+impl ResponseCacheOptions {
+    setters! {
+        store: Arc<dyn ResponseCache> => store,
+        partition: String => Some(partition),
+        max_body_size: usize => max_body_size,
+        vary: Vec<HeaderName> => vary,
+    }
+}
+
+impl Default for ResponseCacheOptions {
+    fn default() -> Self {
+        Self {
+            store: Arc::new(MemoryResponseCache::new(16 * 1024 * 1024)),
+            partition: None,
+            max_body_size: 1024 * 1024,
+            vary: vec![ACCEPT],
+        }
+    }
+}
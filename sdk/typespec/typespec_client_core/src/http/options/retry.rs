@@ -5,9 +5,12 @@ use crate::http::policies::{
     ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy, RetryPolicy,
 };
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// The number of first attempts whose earned retries a [`RetryBudget`] can accumulate.
+const RETRY_BUDGET_WINDOW: f64 = 100.0;
+
 /// The algorithm to apply when calculating the delay between retry attempts.
 #[derive(Clone)]
 enum RetryMode {
@@ -50,6 +53,19 @@ impl Default for RetryMode {
 /// when a retry is attempted.
 ///
 /// The default is an exponential retry policy using the default `ExponentialRetryOptions`.
+///
+/// Retry options are typically set on [`ClientOptions`](crate::http::ClientOptions) for all calls made by a client,
+/// but can be overridden for a single call by adding them to the [`Context`](crate::http::Context) for that call.
+///
+/// # Example
+///
+/// Disabling retries for a single call.
+/// ```
+/// # use typespec_client_core::http::{ClientMethodOptions, Context, RetryOptions};
+/// let options = ClientMethodOptions {
+///     context: Context::new().with_value(RetryOptions::none()),
+/// };
+/// ```
 #[derive(Clone, Debug, Default)]
 pub struct RetryOptions {
     /// The algorithm to use for calculating retry delays.
@@ -92,11 +108,15 @@ impl RetryOptions {
                 options.max_retries,
                 options.max_total_elapsed,
                 options.max_delay,
+                options.jitter,
+                options.budget.clone(),
             )),
             RetryMode::Fixed(options) => Arc::new(FixedRetryPolicy::new(
                 options.delay,
                 options.max_retries,
                 options.max_total_elapsed,
+                options.jitter,
+                options.budget.clone(),
             )),
             RetryMode::Custom(c) => c.clone(),
             RetryMode::None => Arc::new(NoRetryPolicy::default()),
@@ -139,6 +159,16 @@ pub struct ExponentialRetryOptions {
     ///
     /// The default is 30 seconds. For SRE reasons, this is only respected when above 1 second.
     pub max_delay: Duration,
+
+    /// How to randomize the delay between retry attempts.
+    ///
+    /// The default is [`Jitter::Additive`].
+    pub jitter: Jitter,
+
+    /// A budget shared by all requests of a client that limits the number of retries.
+    ///
+    /// The default is `None`.
+    pub budget: Option<RetryBudget>,
 }
 
 impl ExponentialRetryOptions {
@@ -147,6 +177,8 @@ impl ExponentialRetryOptions {
         max_retries: u32 => max_retries,
         max_total_elapsed: Duration => max_total_elapsed,
         max_delay: Duration => max_delay,
+        jitter: Jitter => jitter,
+        budget: RetryBudget => Some(budget),
     }
 }
 
@@ -157,6 +189,8 @@ impl Default for ExponentialRetryOptions {
             max_retries: 8,
             max_total_elapsed: Duration::from_secs(60),
             max_delay: Duration::from_secs(30),
+            jitter: Jitter::default(),
+            budget: None,
         }
     }
 }
@@ -189,6 +223,16 @@ pub struct FixedRetryOptions {
     ///
     /// The default is 1 minute.
     pub max_total_elapsed: Duration,
+
+    /// How to randomize the delay between retry attempts.
+    ///
+    /// The default is [`Jitter::Additive`].
+    pub jitter: Jitter,
+
+    /// A budget shared by all requests of a client that limits the number of retries.
+    ///
+    /// The default is `None`.
+    pub budget: Option<RetryBudget>,
 }
 
 impl FixedRetryOptions {
@@ -199,6 +243,10 @@ impl FixedRetryOptions {
         max_retries: u32 => max_retries,
         #[doc = "Set the maximum permissible elapsed time since starting to retry."]
         max_total_elapsed: Duration => max_total_elapsed,
+        #[doc = "Set how to randomize the delay between retry attempts."]
+        jitter: Jitter => jitter,
+        #[doc = "Set a budget shared by all requests of a client that limits the number of retries."]
+        budget: RetryBudget => Some(budget),
     }
 }
 
@@ -208,6 +256,94 @@ impl Default for FixedRetryOptions {
             delay: Duration::from_millis(200),
             max_retries: 8,
             max_total_elapsed: Duration::from_secs(60),
+            jitter: Jitter::default(),
+            budget: None,
+        }
+    }
+}
+
+/// How to randomize the delay between retry attempts.
+///
+/// Randomizing delays spreads out retries from many clients that failed at the same time,
+/// rather than having them all retry at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// Adds a random delay of up to 256 milliseconds to the computed delay.
+    ///
+    /// This is the default.
+    #[default]
+    Additive,
+
+    /// Chooses a random delay between zero and the computed delay.
+    Full,
+
+    /// Chooses a random delay between half the computed delay and the computed delay.
+    Equal,
+
+    /// Chooses a random delay between the initial delay and three times the previous delay.
+    ///
+    /// For exponential retries, the delay is limited to the maximum delay;
+    /// for fixed retries, the delay is limited to three times the configured delay.
+    Decorrelated,
+}
+
+/// A budget that limits retries to a ratio of first attempts.
+///
+/// Clones of a `RetryBudget` share the same budget, so setting it on the [`RetryOptions`] for a client limits
+/// retries across all of its requests. Each first attempt earns `ratio` retries and each retry spends one;
+/// a request is not retried once the budget is spent. The budget starts with `min_retries` retries
+/// and accumulates at most `min_retries` plus the retries earned by 100 first attempts.
+///
+/// # Example
+///
+/// Allowing at most one retry for every ten requests, beyond an initial ten retries.
+/// ```
+/// # use typespec_client_core::http::{ExponentialRetryOptions, RetryBudget, RetryOptions};
+/// RetryOptions::exponential(
+///    ExponentialRetryOptions::default()
+///        .budget(RetryBudget::new(0.1, 10)),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct RetryBudget {
+    ratio: f64,
+    max_balance: f64,
+    balance: Arc<Mutex<f64>>,
+}
+
+impl RetryBudget {
+    /// Creates a budget allowing `ratio` retries per first attempt in addition to `min_retries`.
+    pub fn new(ratio: f64, min_retries: u32) -> Self {
+        let ratio = ratio.max(0.0);
+        let min_retries = f64::from(min_retries);
+        Self {
+            ratio,
+            max_balance: min_retries + ratio * RETRY_BUDGET_WINDOW,
+            balance: Arc::new(Mutex::new(min_retries)),
+        }
+    }
+
+    /// Records a first attempt.
+    pub(crate) fn deposit(&self) {
+        let mut balance = self.balance.lock().expect("lock poisoned");
+        *balance = (*balance + self.ratio).min(self.max_balance);
+    }
+
+    /// Spends a retry from the budget, returning `false` if the budget is spent.
+    pub(crate) fn try_withdraw(&self) -> bool {
+        let mut balance = self.balance.lock().expect("lock poisoned");
+        if *balance < 1.0 {
+            return false;
         }
+        *balance -= 1.0;
+        true
+    }
+}
+
+impl PartialEq for RetryBudget {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.balance, &other.balance)
     }
 }
+
+impl Eq for RetryBudget {}
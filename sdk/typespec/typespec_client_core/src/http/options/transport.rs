@@ -1,7 +1,9 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::http::{clients, policies::Policy, Context, HttpClient, Request, Response};
+use crate::http::{
+    clients, policies::Policy, Context, HttpClient, HttpClientOptions, Request, Response,
+};
 use std::sync::Arc;
 use typespec::error::Result;
 
@@ -27,6 +29,13 @@ impl TransportOptions {
         Self { inner }
     }
 
+    /// Creates a new `TransportOptions` using the built-in `HttpClient` configured with the given options.
+    ///
+    /// Returns an error if the options are not valid, e.g. a certificate cannot be parsed.
+    pub fn with_http_client_options(options: &HttpClientOptions) -> Result<Self> {
+        Ok(Self::new(clients::new_http_client_with_options(options)?))
+    }
+
     /// Creates a new `TransportOptions` using the custom policy.
     ///
     /// This policy is expected to be the last policy in the pipeline.
@@ -1,12 +1,24 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use std::{future::Future, pin::Pin};
+use std::{
+    future::Future,
+    num::NonZeroU32,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
-use futures::{stream::unfold, Stream};
-use typespec::Error;
+use futures::{channel::mpsc, future::poll_fn, stream::unfold, Stream, StreamExt, TryStreamExt};
+use typespec::{
+    error::{ErrorKind, ResultExt},
+    Error,
+};
 
-use crate::http::{headers::HeaderName, Response};
+use crate::{
+    async_runtime::AsyncRuntime,
+    http::{headers::HeaderName, Cancellation, Context, Model, Response},
+};
 
 /// The result of fetching a single page from a [`Pager`], whether the `Pager` should continue or is complete.
 pub enum PagerResult<T, C> {
@@ -35,16 +47,61 @@ impl<T> PagerResult<T, String> {
     }
 }
 
+/// A page model that contains the items it returned.
+///
+/// Implement `Page` for page models returned by a [`Pager`] to iterate over the items in all pages
+/// using [`Pager::into_items`].
+pub trait Page {
+    /// The type of items in the page.
+    type Item;
+
+    /// The type of iterator over the items in the page.
+    type IntoIter: Iterator<Item = Self::Item>;
+
+    /// Consumes the page and returns an iterator over its items.
+    fn into_items(self) -> Self::IntoIter;
+}
+
+/// Options for client methods that return a [`Pager`].
+#[derive(Clone, Debug, Default)]
+pub struct PagerOptions {
+    /// A continuation token returned by [`Pager::continuation_token`] to resume fetching pages from.
+    ///
+    /// If `None`, pages are fetched from the beginning.
+    pub continuation_token: Option<String>,
+
+    /// The maximum number of items to request in each page.
+    ///
+    /// This is a hint: the service may return fewer items, or ignore it altogether.
+    /// If `None`, the service's default page size is used.
+    pub max_page_size: Option<NonZeroU32>,
+
+    /// The number of pages to fetch ahead while the current page is being processed.
+    ///
+    /// See [`Pager::with_prefetch`]. The default is 0, which fetches pages only as they are requested.
+    pub prefetch: usize,
+}
+
+impl PagerOptions {
+    setters! {
+        continuation_token: String => Some(continuation_token),
+        max_page_size: NonZeroU32 => Some(max_page_size),
+        prefetch: usize => prefetch,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type PageStream<T> = Pin<Box<dyn Stream<Item = Result<T, Error>> + Send>>;
+
+#[cfg(target_arch = "wasm32")]
+type PageStream<T> = Pin<Box<dyn Stream<Item = Result<T, Error>>>>;
+
 /// Represents a paginated result across multiple requests.
 #[pin_project::pin_project]
 pub struct Pager<T> {
     #[pin]
-    #[cfg(not(target_arch = "wasm32"))]
-    stream: Pin<Box<dyn Stream<Item = Result<Response<T>, Error>> + Send>>,
-
-    #[pin]
-    #[cfg(target_arch = "wasm32")]
-    stream: Pin<Box<dyn Stream<Item = Result<Response<T>, Error>>>>,
+    stream: PageStream<Response<T>>,
+    continuation: Option<Arc<Mutex<Option<String>>>>,
 }
 
 impl<T> Pager<T> {
@@ -60,6 +117,8 @@ impl<T> Pager<T> {
     /// * `Ok((response, None))` - The request succeeded, and there are no more pages. The response will be yielded to the stream, the stream will end, and the callback will not be called again.
     /// * `Err(..)` - The request failed. The error will be yielded to the stream, the stream will end, and the callback will not be called again.
     ///
+    /// Use [`Pager::from_resumable_callback`] instead to get continuation tokens from the `Pager` and resume from them later.
+    ///
     /// ## Examples
     ///
     /// ```rust,no_run
@@ -94,30 +153,251 @@ impl<T> Pager<T> {
     >(
         make_request: F,
     ) -> Self {
+        Self {
+            stream: unfold_pages(State::Init, make_request, |_| {}),
+            continuation: None,
+        }
+    }
+
+    /// Creates a [`Pager<T>`] that can be resumed from a continuation token, from a callback that will be called repeatedly to request each page.
+    ///
+    /// The callback is called the same way as for [`Pager::from_callback`], except that the first time it is called with the
+    /// `continuation_token` parsed into a `C`, if any. After each page, [`Pager::continuation_token`] returns the continuation formatted as a string.
+    ///
+    /// Returns an error if the `continuation_token` cannot be parsed.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// # use typespec_client_core::http::{Context, Pager, PagerOptions, PagerResult, Pipeline, Request, Response, Method, headers::HeaderName};
+    /// # let pipeline: Pipeline = panic!("Not a runnable example");
+    /// # let options = PagerOptions::default();
+    /// # struct MyModel;
+    /// let url = "https://example.com/my_paginated_api".parse().unwrap();
+    /// let mut base_req = Request::new(url, Method::Get);
+    /// if let Some(max_page_size) = options.max_page_size {
+    ///     base_req.insert_header("x-max-page-size", max_page_size.to_string());
+    /// }
+    /// let pager = Pager::from_resumable_callback(options.continuation_token, move |continuation: Option<String>| {
+    ///     let pipeline = pipeline.clone();
+    ///     let mut req = base_req.clone();
+    ///     async move {
+    ///         if let Some(continuation) = continuation {
+    ///             req.insert_header("x-continuation", continuation);
+    ///         }
+    ///         let resp: Response<MyModel> = pipeline
+    ///           .send(&Context::new(), &mut req)
+    ///           .await?;
+    ///         Ok(PagerResult::from_response_header(resp, &HeaderName::from_static("x-next-continuation")))
+    ///     }
+    /// })?;
+    /// # Ok::<(), typespec::Error>(())
+    /// ```
+    pub fn from_resumable_callback<
+        #[cfg(not(target_arch = "wasm32"))] C: FromStr + ToString + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<C>) -> Fut + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<T, C>, typespec::Error>> + Send + 'static,
+        #[cfg(target_arch = "wasm32")] C: FromStr + ToString + 'static,
+        #[cfg(target_arch = "wasm32")] F: Fn(Option<C>) -> Fut + 'static,
+        #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<T, C>, typespec::Error>> + 'static,
+    >(
+        continuation_token: Option<String>,
+        make_request: F,
+    ) -> typespec::Result<Self>
+    where
+        C::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let state = match &continuation_token {
+            Some(token) => State::Continuation(
+                token.parse().with_context(ErrorKind::DataConversion, || {
+                    format!("invalid continuation token: {token}")
+                })?,
+            ),
+            None => State::Init,
+        };
+        let continuation = Arc::new(Mutex::new(continuation_token));
+        let stream = unfold_pages(state, make_request, {
+            let continuation = continuation.clone();
+            move |next: Option<&C>| {
+                *continuation.lock().expect("lock poisoned") = next.map(ToString::to_string);
+            }
+        });
+        Ok(Self {
+            stream,
+            continuation: Some(continuation),
+        })
+    }
+
+    /// Gets the continuation token to resume fetching pages after the last page returned.
+    ///
+    /// Pass this token to a client method that returns a [`Pager`] to resume from the next page later.
+    /// Before the first page is returned, this is the token the `Pager` was created with.
+    /// Returns `None` after the last page has been returned, or if the `Pager` was not created with [`Pager::from_resumable_callback`].
+    pub fn continuation_token(&self) -> Option<String> {
+        self.continuation
+            .as_ref()
+            .and_then(|continuation| continuation.lock().expect("lock poisoned").clone())
+    }
+
+    /// Stops fetching pages once the operation is cancelled or its deadline passes.
+    ///
+    /// If the `context` contains a [`Deadline`](crate::http::Deadline) or [`CancellationToken`](crate::http::CancellationToken),
+    /// a page that is being fetched when the operation is cancelled or the deadline passes is dropped,
+    /// an [`ErrorKind::Cancelled`](typespec::error::ErrorKind::Cancelled) error is yielded, and the stream ends.
+    pub fn with_cancellation(self, context: &Context) -> Self
+    where
+        T: 'static,
+    {
+        let cancellation = Cancellation::from_context(context);
+        if cancellation.is_none() {
+            return self;
+        }
+
         let stream = unfold(
-            // We flow the `make_request` callback through the state value so that we can avoid cloning.
-            (State::Init, make_request),
-            |(state, make_request)| async move {
-                let result = match state {
-                    State::Init => make_request(None).await,
-                    State::Continuation(c) => make_request(Some(c)).await,
-                    State::Done => return None,
+            (Some(self.stream), cancellation),
+            |(stream, cancellation)| async move {
+                let mut stream = stream?;
+                match cancellation.run(async { Ok(stream.next().await) }).await {
+                    Ok(Some(page)) => Some((page, (Some(stream), cancellation))),
+                    Ok(None) => None,
+                    Err(e) => Some((Err(e), (None, cancellation))),
+                }
+            },
+        );
+        Self {
+            stream: Box::pin(stream),
+            continuation: self.continuation,
+        }
+    }
+
+    /// Fetches up to `pages` pages ahead while the current page is being processed.
+    ///
+    /// Pages are fetched in order by a background task spawned on the `runtime`, e.g. from [`Pipeline::runtime`](crate::http::Pipeline::runtime),
+    /// and each page's body is read before it is returned. Once `pages` pages are waiting to be returned, no more pages are fetched
+    /// until the next one is returned. Dropping the `Pager` stops fetching pages after any page currently being fetched.
+    /// If the background task ends before fetching the last page, e.g. because it panicked, an error is yielded and the stream ends.
+    ///
+    /// [`Pager::continuation_token`] still returns the continuation token after the last page returned, not the last page fetched.
+    /// If `pages` is 0, pages are fetched only as they are requested.
+    pub fn with_prefetch(self, pages: usize, runtime: Arc<dyn AsyncRuntime>) -> Self
+    where
+        T: 'static,
+    {
+        if pages == 0 {
+            return self;
+        }
+
+        // A channel has one slot per sender in addition to its buffer.
+        // Each page is sent with its continuation token, followed by `None` after the last page.
+        let (mut tx, rx) = mpsc::channel(pages - 1);
+        let fetched = self.continuation.clone();
+        let mut stream = self.stream;
+        let prefetch = async move {
+            // Wait for a free slot before fetching each page so that no more than `pages` pages are fetched ahead.
+            while poll_fn(|cx| tx.poll_ready(cx)).await.is_ok() {
+                let Some(page) = stream.next().await else {
+                    let _ = tx.start_send(None);
+                    break;
                 };
-                let (response, next_state) = match result {
-                    Err(e) => return Some((Err(e), (State::Done, make_request))),
-                    Ok(PagerResult::Continue {
-                        response,
-                        continuation,
-                    }) => (Ok(response), State::Continuation(continuation)),
-                    Ok(PagerResult::Complete { response }) => (Ok(response), State::Done),
+                let continuation = fetched
+                    .as_ref()
+                    .and_then(|continuation| continuation.lock().expect("lock poisoned").clone());
+                let page = match page.map(Response::deconstruct) {
+                    Ok((status, headers, body)) => {
+                        body.collect().await.map(|body| (status, headers, body))
+                    }
+                    Err(err) => Err(err),
                 };
+                let failed = page.is_err();
+                if tx.start_send(Some((page, continuation))).is_err() || failed {
+                    break;
+                }
+            }
+        };
+        // Dropping the handle does not cancel the task; it ends when the pages or the receiver do.
+        drop(runtime.spawn(Box::pin(prefetch)));
 
-                // Flow 'make_request' through to avoid cloning
-                Some((response, (next_state, make_request)))
-            },
-        );
+        let continuation = self.continuation.map(|fetched| {
+            let current = fetched.lock().expect("lock poisoned").clone();
+            Arc::new(Mutex::new(current))
+        });
+        let stream = unfold(Some(rx), {
+            let continuation = continuation.clone();
+            move |rx| {
+                let continuation = continuation.clone();
+                async move {
+                    let mut rx = rx?;
+                    match rx.next().await {
+                        Some(Some((page, token))) => {
+                            if let Some(continuation) = &continuation {
+                                *continuation.lock().expect("lock poisoned") = token;
+                            }
+                            let page = page.map(|(status, headers, body)| {
+                                Response::from_bytes(status, headers, body)
+                            });
+                            // The task stops after an error, so stop receiving too.
+                            let rx = page.is_ok().then_some(rx);
+                            Some((page, rx))
+                        }
+                        Some(None) => None,
+                        None => Some((
+                            Err(Error::message(
+                                ErrorKind::Other,
+                                "the task prefetching pages ended before the last page",
+                            )),
+                            None,
+                        )),
+                    }
+                }
+            }
+        });
         Self {
             stream: Box::pin(stream),
+            continuation,
+        }
+    }
+}
+
+impl<T> Pager<T>
+where
+    T: Page + Model + 'static,
+    T::IntoIter: 'static,
+{
+    /// Converts the `Pager` into a stream of the items in all pages.
+    ///
+    /// Each page is deserialized using [`Response::into_body`] as it is needed.
+    /// An error fetching or deserializing a page is yielded and the stream ends.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn into_items(self) -> ItemPager<T::Item>
+    where
+        T: Send,
+        T::IntoIter: Send,
+    {
+        ItemPager {
+            stream: Box::pin(
+                self.then(|page| async move {
+                    let items = page?.into_body().await?.into_items();
+                    Ok::<_, Error>(futures::stream::iter(items.map(Ok)))
+                })
+                .try_flatten(),
+            ),
+        }
+    }
+
+    /// Converts the `Pager` into a stream of the items in all pages.
+    ///
+    /// Each page is deserialized using [`Response::into_body`] as it is needed.
+    /// An error fetching or deserializing a page is yielded and the stream ends.
+    #[cfg(target_arch = "wasm32")]
+    pub fn into_items(self) -> ItemPager<T::Item> {
+        ItemPager {
+            stream: Box::pin(
+                self.then(|page| async move {
+                    let items = page?.into_body().await?.into_items();
+                    Ok::<_, Error>(futures::stream::iter(items.map(Ok)))
+                })
+                .try_flatten(),
+            ),
         }
     }
 }
@@ -139,6 +419,77 @@ impl<T> std::fmt::Debug for Pager<T> {
     }
 }
 
+/// A stream of the items in all pages of a [`Pager`], returned by [`Pager::into_items`].
+#[pin_project::pin_project]
+pub struct ItemPager<T> {
+    #[pin]
+    stream: PageStream<T>,
+}
+
+impl<T> futures::Stream for ItemPager<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.project().stream.poll_next(cx)
+    }
+}
+
+impl<T> std::fmt::Debug for ItemPager<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemPager").finish_non_exhaustive()
+    }
+}
+
+/// Streams pages by calling `make_request` with each continuation, and calls `on_page` with the continuation after each page.
+fn unfold_pages<
+    T,
+    #[cfg(not(target_arch = "wasm32"))] C: Send + 'static,
+    #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<C>) -> Fut + Send + 'static,
+    #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<T, C>, typespec::Error>> + Send + 'static,
+    #[cfg(not(target_arch = "wasm32"))] G: Fn(Option<&C>) + Send + 'static,
+    #[cfg(target_arch = "wasm32")] C: 'static,
+    #[cfg(target_arch = "wasm32")] F: Fn(Option<C>) -> Fut + 'static,
+    #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<T, C>, typespec::Error>> + 'static,
+    #[cfg(target_arch = "wasm32")] G: Fn(Option<&C>) + 'static,
+>(
+    state: State<C>,
+    make_request: F,
+    on_page: G,
+) -> PageStream<Response<T>> {
+    let stream = unfold(
+        // We flow the `make_request` callback through the state value so that we can avoid cloning.
+        (state, (make_request, on_page)),
+        |(state, (make_request, on_page))| async move {
+            let result = match state {
+                State::Init => make_request(None).await,
+                State::Continuation(c) => make_request(Some(c)).await,
+                State::Done => return None,
+            };
+            let (response, next_state) = match result {
+                Err(e) => return Some((Err(e), (State::Done, (make_request, on_page)))),
+                Ok(PagerResult::Continue {
+                    response,
+                    continuation,
+                }) => {
+                    on_page(Some(&continuation));
+                    (Ok(response), State::Continuation(continuation))
+                }
+                Ok(PagerResult::Complete { response }) => {
+                    on_page(None);
+                    (Ok(response), State::Done)
+                }
+            };
+
+            // Flow 'make_request' through to avoid cloning
+            Some((response, (next_state, (make_request, on_page))))
+        },
+    );
+    Box::pin(stream)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum State<T> {
     Init,
@@ -148,16 +499,27 @@ enum State<T> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
 
-    use futures::StreamExt;
+    use futures::{StreamExt, TryStreamExt};
     use serde::Deserialize;
     use typespec_macros::Model;
 
-    use crate::http::{
-        headers::{HeaderName, HeaderValue},
-        Pager, PagerResult, Response, StatusCode,
+    use crate::{
+        async_runtime::{self, AsyncRuntime, SleepFuture, TaskFuture, TaskHandle},
+        http::{
+            headers::{HeaderName, HeaderValue, Headers},
+            CancellationToken, Context, Page, Pager, PagerResult, Response, StatusCode,
+        },
     };
+    use typespec::{error::ErrorKind, Error};
 
     #[tokio::test]
     pub async fn standard_pagination() {
@@ -284,4 +646,142 @@ mod tests {
         assert_eq!(&typespec::error::ErrorKind::Other, err.kind());
         assert_eq!("yon request didst fail", format!("{}", err));
     }
+
+    #[tokio::test]
+    pub async fn cancellation_stops_pagination() {
+        let token = CancellationToken::new();
+        let ctx = Context::new().with_value(token.clone());
+        let mut pager: Pager<()> = Pager::from_callback(|continuation: Option<usize>| async move {
+            let page = continuation.unwrap_or_default();
+            Ok(PagerResult::Continue {
+                response: Response::from_bytes(StatusCode::Ok, Headers::new(), ""),
+                continuation: page + 1,
+            })
+        })
+        .with_cancellation(&ctx);
+
+        assert!(pager.next().await.unwrap().is_ok());
+        assert!(pager.next().await.unwrap().is_ok());
+        token.cancel();
+
+        let err = pager.next().await.unwrap().unwrap_err();
+        assert_eq!(&typespec::error::ErrorKind::Cancelled, err.kind());
+        assert!(pager.next().await.is_none());
+    }
+
+    #[derive(Model, Deserialize, Debug, PartialEq, Eq)]
+    #[typespec(crate = "crate")]
+    struct ItemsPage {
+        items: Vec<usize>,
+    }
+
+    impl Page for ItemsPage {
+        type Item = usize;
+        type IntoIter = std::vec::IntoIter<usize>;
+
+        fn into_items(self) -> Self::IntoIter {
+            self.items.into_iter()
+        }
+    }
+
+    /// Returns pages of two items until page 3, continuing with the next page number.
+    async fn items_page(page: Option<usize>) -> typespec::Result<PagerResult<ItemsPage, usize>> {
+        let page = page.unwrap_or(1);
+        let response = Response::from_bytes(
+            StatusCode::Ok,
+            Headers::new(),
+            format!(r#"{{"items":[{},{}]}}"#, page * 10, page * 10 + 1),
+        );
+        Ok(match page {
+            3 => PagerResult::Complete { response },
+            _ => PagerResult::Continue {
+                response,
+                continuation: page + 1,
+            },
+        })
+    }
+
+    #[tokio::test]
+    pub async fn iterates_items() {
+        let items: Vec<usize> = Pager::from_callback(items_page)
+            .into_items()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(vec![10, 11, 20, 21, 30, 31], items);
+    }
+
+    #[tokio::test]
+    pub async fn resumes_from_continuation_token() {
+        let mut pager = Pager::from_resumable_callback(None, items_page).unwrap();
+        assert_eq!(None, pager.continuation_token());
+        pager.next().await.unwrap().unwrap();
+        let token = pager.continuation_token();
+        assert_eq!(Some("2".to_string()), token);
+
+        let mut pager = Pager::from_resumable_callback(token, items_page).unwrap();
+        assert_eq!(Some("2".to_string()), pager.continuation_token());
+        let page = pager
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_body()
+            .await
+            .unwrap();
+        assert_eq!(vec![20, 21], page.items);
+        pager.next().await.unwrap().unwrap();
+        assert_eq!(None, pager.continuation_token());
+        assert!(pager.next().await.is_none());
+
+        let err = Pager::from_resumable_callback(Some("two".to_string()), items_page).unwrap_err();
+        assert_eq!(&typespec::error::ErrorKind::DataConversion, err.kind());
+    }
+
+    #[tokio::test]
+    pub async fn prefetches_pages() {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let mut pager = Pager::from_resumable_callback(None, {
+            let fetched = fetched.clone();
+            move |page: Option<usize>| {
+                fetched.fetch_add(1, Ordering::SeqCst);
+                items_page(page)
+            }
+        })
+        .unwrap()
+        .with_prefetch(1, async_runtime::default_runtime());
+
+        let page = pager.next().await.unwrap().unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // The first page was returned and only the next page was fetched ahead.
+        assert_eq!(2, fetched.load(Ordering::SeqCst));
+        assert_eq!(Some("2".to_string()), pager.continuation_token());
+        assert_eq!(vec![10, 11], page.into_body().await.unwrap().items);
+
+        let items: Vec<usize> = pager.into_items().try_collect().await.unwrap();
+        assert_eq!(vec![20, 21, 30, 31], items);
+        assert_eq!(3, fetched.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    pub async fn prefetch_task_failure_is_an_error() {
+        /// Drops spawned tasks without running them.
+        #[derive(Debug)]
+        struct Dropping;
+
+        impl AsyncRuntime for Dropping {
+            fn sleep(&self, duration: Duration) -> SleepFuture {
+                Box::pin(tokio::time::sleep(duration))
+            }
+
+            fn spawn(&self, _future: TaskFuture) -> TaskHandle {
+                Box::pin(async { Err(Error::message(ErrorKind::Other, "dropped")) })
+            }
+        }
+
+        let mut pager = Pager::from_callback(items_page).with_prefetch(1, Arc::new(Dropping));
+        let err = pager.next().await.unwrap().unwrap_err();
+        assert_eq!(&ErrorKind::Other, err.kind());
+        assert!(pager.next().await.is_none());
+    }
 }
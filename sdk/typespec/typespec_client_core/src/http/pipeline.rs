@@ -1,11 +1,25 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::http::{
-    policies::{CustomHeadersPolicy, Policy, TransportPolicy},
-    ClientOptions, Context, Request, Response, RetryOptions,
+#[cfg(any(feature = "gzip", feature = "brotli", feature = "zstd"))]
+use crate::http::policies::DecompressionPolicy;
+#[cfg(feature = "gzip")]
+use crate::http::policies::RequestCompressionPolicy;
+#[cfg(feature = "distributed_tracing")]
+use crate::http::policies::{DistributedTracingPolicy, RequestTracingPolicy};
+use crate::{
+    async_runtime::{self, AsyncRuntime},
+    date::{Clock, SystemClock},
+    http::{
+        policies::{
+            BandwidthLimitPolicy, CircuitBreakerPolicy, ContextRetryPolicy, CustomHeadersPolicy,
+            LoggingPolicy, MetricsPolicy, Policy, RateLimitPolicy, RequestMetricsPolicy,
+            ResponseCachePolicy, TimeoutPolicy, TransferProgressPolicy, TransportPolicy,
+        },
+        ClientOptions, Context, Request, Response,
+    },
 };
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 /// Execution pipeline.
 ///
@@ -14,24 +28,42 @@ use std::sync::Arc;
 /// 1. Client library-specified per-call policies are executed. Per-call policies can fail and bail out of the pipeline
 ///    immediately.
 /// 2. User-specified per-call policies are executed.
-/// 3. Telemetry policy.
+/// 3. Telemetry policy and, if a tracer is configured, the distributed tracing policy.
+///    If configured, the metrics policy records metrics for each call.
+///    If configured, the request compression policy compresses the request body once for all tries.
+///    The transfer progress policy reports upload and download progress if the [`Context`] contains a
+///    [`ProgressCallback`](crate::stream::ProgressCallback).
+///    If configured, the bandwidth limit policy limits the bytes per second sent and received by bodies.
 /// 4. Retry policy. It allows to re-execute the following policies.
+///    If metrics are configured, the request metrics policy counts each try.
+///    If configured, the circuit breaker policy fails each try immediately while the circuit for the host is open,
+///    and the rate limit policy delays each try until it can be sent within the rate limit.
+///    If configured, the response cache policy revalidates cached `GET` responses and returns them if not modified.
 /// 5. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
 ///    in case of retries.
 /// 6. User-specified per-retry policies are executed.
 /// 7. Authorization policy. Authorization can depend on the HTTP headers and/or the request body so it
 ///    must be executed right before sending the request to the transport. Also, the authorization
 ///    can depend on the current time so it must be executed at every retry.
-/// 8. Transport policy. Transport policy is always the last policy and is the policy that
-///    actually constructs the `Response` to be passed up the pipeline.
+/// 8. Request tracing policy, if a tracer is configured.
+/// 9. Logging policy. Requests and responses are logged with header and query parameter values redacted.
+/// 10. Timeout policy, if a per-try timeout is configured. A try that times out is retried by the retry policy.
+/// 11. Decompression policy, if the `gzip`, `brotli`, or `zstd` feature is enabled.
+/// 12. Transport policy. Transport policy is always the last policy and is the policy that
+///     actually constructs the `Response` to be passed up the pipeline.
 ///
 /// A pipeline is immutable. In other words a policy can either succeed and call the following
 /// policy of fail and return to the calling policy. Arbitrary policy "skip" must be avoided (but
 /// cannot be enforced by code). All policies except Transport policy can assume there is another following policy (so
 /// `self.pipeline[0]` is always valid).
+///
+/// If [`ClientOptions::runtime`] or [`ClientOptions::clock`] are set, they are added to the [`Context`] passed to each policy
+/// unless the `Context` already contains an `Arc<dyn AsyncRuntime>` or `Arc<dyn Clock>` respectively.
 #[derive(Debug, Clone)]
 pub struct Pipeline {
     pipeline: Vec<Arc<dyn Policy>>,
+    runtime: Option<Arc<dyn AsyncRuntime>>,
+    clock: Option<Arc<dyn Clock>>,
 }
 
 impl Pipeline {
@@ -47,27 +79,88 @@ impl Pipeline {
                 + per_call_policies.len()
                 + options.per_try_policies.len()
                 + per_retry_policies.len()
-                + 2,
+                + 8,
         );
 
         pipeline.extend_from_slice(&per_call_policies);
         pipeline.extend_from_slice(&options.per_call_policies);
 
+        #[cfg(feature = "distributed_tracing")]
+        if let Some(tracer) = &options.tracer {
+            pipeline.push(Arc::new(DistributedTracingPolicy::new(tracer.clone())));
+        }
+
+        let metrics = options.metrics.is_some();
+        if let Some(metrics) = options.metrics {
+            pipeline.push(Arc::new(MetricsPolicy::new(metrics)));
+        }
+
         pipeline.push(Arc::new(CustomHeadersPolicy::default()));
 
-        // TODO: Consider whether this should be initially customizable as we onboard more services.
-        let retry_policy = RetryOptions::default().to_policy();
-        pipeline.push(retry_policy);
+        #[cfg(feature = "gzip")]
+        if let Some(compression) = options.compression {
+            pipeline.push(Arc::new(RequestCompressionPolicy::new(compression)));
+        }
+
+        pipeline.push(Arc::new(TransferProgressPolicy));
+
+        if let Some(bandwidth) = options.bandwidth {
+            pipeline.push(Arc::new(BandwidthLimitPolicy::new(bandwidth)));
+        }
+
+        // The retry policy can be overridden for a single call by passing `RetryOptions` in the `Context`.
+        let retry_policy = ContextRetryPolicy::new(&options.retry.unwrap_or_default());
+        pipeline.push(Arc::new(retry_policy));
+
+        if metrics {
+            pipeline.push(Arc::new(RequestMetricsPolicy));
+        }
+
+        if let Some(circuit_breaker) = options.circuit_breaker {
+            pipeline.push(Arc::new(CircuitBreakerPolicy::new(circuit_breaker)));
+        }
+
+        if let Some(rate_limit) = options.rate_limit {
+            pipeline.push(Arc::new(RateLimitPolicy::new(rate_limit)));
+        }
+
+        if let Some(response_cache) = options.response_cache {
+            pipeline.push(Arc::new(ResponseCachePolicy::new(response_cache)));
+        }
 
         pipeline.extend_from_slice(&per_retry_policies);
         pipeline.extend_from_slice(&options.per_try_policies);
 
+        let logging = options.logging.unwrap_or_default();
+
+        #[cfg(feature = "distributed_tracing")]
+        if let Some(tracer) = &options.tracer {
+            pipeline.push(Arc::new(RequestTracingPolicy::new(
+                tracer.clone(),
+                logging.clone(),
+            )));
+        }
+
+        let logging_policy = LoggingPolicy::new(logging);
+        pipeline.push(Arc::new(logging_policy));
+
+        if let Some(timeout) = options.per_try_timeout {
+            pipeline.push(Arc::new(TimeoutPolicy::new(timeout)));
+        }
+
+        #[cfg(any(feature = "gzip", feature = "brotli", feature = "zstd"))]
+        pipeline.push(Arc::new(DecompressionPolicy::default()));
+
         let transport: Arc<dyn Policy> =
             Arc::new(TransportPolicy::new(options.transport.unwrap_or_default()));
 
         pipeline.push(transport);
 
-        Self { pipeline }
+        Self {
+            pipeline,
+            runtime: options.runtime,
+            clock: options.clock,
+        }
     }
 
     pub fn replace_policy(&mut self, policy: Arc<dyn Policy>, position: usize) -> Arc<dyn Policy> {
@@ -78,13 +171,46 @@ impl Pipeline {
         &self.pipeline
     }
 
+    /// Gets the [`AsyncRuntime`] to run background tasks for a call with the `ctx` on.
+    ///
+    /// This is the runtime in the `ctx`, if any; otherwise, [`ClientOptions::runtime`] if set, or the
+    /// [`default_runtime`](crate::async_runtime::default_runtime).
+    pub fn runtime(&self, ctx: &Context<'_>) -> Arc<dyn AsyncRuntime> {
+        ctx.value::<Arc<dyn AsyncRuntime>>()
+            .or(self.runtime.as_ref())
+            .cloned()
+            .unwrap_or_else(async_runtime::default_runtime)
+    }
+
+    /// Gets the [`Clock`] for a call with the `ctx`.
+    ///
+    /// This is the clock in the `ctx`, if any; otherwise, [`ClientOptions::clock`] if set, or the
+    /// [`SystemClock`](crate::date::SystemClock).
+    pub fn clock(&self, ctx: &Context<'_>) -> Arc<dyn Clock> {
+        ctx.value::<Arc<dyn Clock>>()
+            .or(self.clock.as_ref())
+            .cloned()
+            .unwrap_or_else(|| Arc::new(SystemClock))
+    }
+
     pub async fn send<T>(
         &self,
         ctx: &Context<'_>,
         request: &mut Request,
     ) -> crate::Result<Response<T>> {
+        let mut ctx = Cow::Borrowed(ctx);
+        if let Some(runtime) = &self.runtime {
+            if ctx.value::<Arc<dyn AsyncRuntime>>().is_none() {
+                ctx.to_mut().insert(runtime.clone());
+            }
+        }
+        if let Some(clock) = &self.clock {
+            if ctx.value::<Arc<dyn Clock>>().is_none() {
+                ctx.to_mut().insert(clock.clone());
+            }
+        }
         self.pipeline[0]
-            .send(ctx, request, &self.pipeline[1..])
+            .send(&ctx, request, &self.pipeline[1..])
             .await
             .map(|resp| resp.with_default_deserialize_type())
     }
@@ -94,13 +220,46 @@ impl Pipeline {
 mod tests {
     use super::*;
     use crate::{
-        http::{headers::Headers, policies::PolicyResult, Method, StatusCode, TransportOptions},
+        async_runtime::RecordingRuntime,
+        date::ManualClock,
+        http::{
+            headers::Headers, policies::PolicyResult, Deadline, FixedRetryOptions, Method,
+            RetryBudget, RetryOptions, StatusCode, TransportOptions,
+        },
         stream::BytesStream,
     };
     use bytes::Bytes;
     use serde::Deserialize;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use typespec::error::ErrorKind;
     use typespec_macros::Model;
 
+    #[derive(Debug, Default)]
+    struct Unavailable {
+        tries: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Unavailable {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.tries.fetch_add(1, Ordering::SeqCst);
+            Ok(Response::from_bytes(
+                StatusCode::ServiceUnavailable,
+                Headers::new(),
+                Bytes::new(),
+            ))
+        }
+    }
+
     #[tokio::test]
     async fn deserializes_response() {
         #[derive(Debug)]
@@ -147,4 +306,178 @@ mod tests {
         assert_eq!(1, model.foo);
         assert_eq!("baz", &model.bar);
     }
+
+    #[tokio::test]
+    async fn uses_client_retry_options() {
+        let transport = Arc::new(Unavailable::default());
+        let options = ClientOptions {
+            retry: Some(RetryOptions::none()),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline
+            .send::<()>(&Context::default(), &mut request)
+            .await
+            .expect_err("expected error status");
+
+        assert_eq!(1, transport.tries.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn overrides_retry_options_from_context() {
+        let transport = Arc::new(Unavailable::default());
+        let options = ClientOptions {
+            retry: Some(RetryOptions::none()),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let ctx = Context::new().with_value(RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(10))
+                .max_retries(2u32),
+        ));
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline
+            .send::<()>(&ctx, &mut request)
+            .await
+            .expect_err("expected error status");
+
+        assert_eq!(3, transport.tries.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_at_deadline() {
+        let transport = Arc::new(Unavailable::default());
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default().delay(Duration::from_secs(60)),
+            )),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let ctx = Context::new().with_value(Deadline::after(Duration::from_millis(50)));
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let err = pipeline
+            .send::<()>(&ctx, &mut request)
+            .await
+            .expect_err("expected deadline to pass");
+
+        assert_eq!(&ErrorKind::Cancelled, err.kind());
+        assert_eq!(1, transport.tries.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn retry_budget_limits_retries() {
+        let transport = Arc::new(Unavailable::default());
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default()
+                    .delay(Duration::from_millis(10))
+                    .max_retries(3u32)
+                    .budget(RetryBudget::new(0.0, 1)),
+            )),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        for _ in 0..2 {
+            let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+            pipeline
+                .send::<()>(&Context::new(), &mut request)
+                .await
+                .expect_err("expected error status");
+        }
+
+        // Only the first request is retried, once, before the budget is spent.
+        assert_eq!(3, transport.tries.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn sleeps_on_client_runtime() {
+        let runtime = Arc::new(RecordingRuntime::default());
+        let transport = Arc::new(Unavailable::default());
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default()
+                    .delay(Duration::from_secs(60))
+                    .max_retries(2u32),
+            )),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            runtime: Some(runtime.clone()),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline
+            .send::<()>(&Context::new(), &mut request)
+            .await
+            .expect_err("expected error status");
+
+        assert_eq!(3, transport.tries.load(Ordering::SeqCst));
+        assert_eq!(2, runtime.sleeps().len());
+    }
+
+    #[tokio::test]
+    async fn expires_retries_with_client_clock() {
+        #[derive(Debug)]
+        struct SlowUnavailable {
+            clock: ManualClock,
+            tries: AtomicUsize,
+        }
+
+        #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+        #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+        impl Policy for SlowUnavailable {
+            async fn send(
+                &self,
+                _ctx: &Context,
+                _request: &mut Request,
+                _next: &[Arc<dyn Policy>],
+            ) -> PolicyResult {
+                self.tries.fetch_add(1, Ordering::SeqCst);
+                self.clock.advance(Duration::from_secs(30));
+                Ok(Response::from_bytes(
+                    StatusCode::ServiceUnavailable,
+                    Headers::new(),
+                    Bytes::new(),
+                ))
+            }
+        }
+
+        let clock = ManualClock::default();
+        let transport = Arc::new(SlowUnavailable {
+            clock: clock.clone(),
+            tries: AtomicUsize::new(0),
+        });
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default()
+                    .delay(Duration::from_millis(1))
+                    .max_retries(10u32)
+                    .max_total_elapsed(Duration::from_secs(60)),
+            )),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            clock: Some(Arc::new(clock)),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline
+            .send::<()>(&Context::new(), &mut request)
+            .await
+            .expect_err("expected error status");
+
+        // Time is measured from the end of the first try, so the third try ends 60 seconds later.
+        assert_eq!(3, transport.tries.load(Ordering::SeqCst));
+    }
 }
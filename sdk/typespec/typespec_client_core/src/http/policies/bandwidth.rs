@@ -0,0 +1,117 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    async_runtime,
+    http::{
+        policies::{Policy, PolicyResult},
+        BandwidthOptions, Context, Request, Response,
+    },
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    http::Body,
+    stream::{BytesStream, ThrottledStream},
+};
+use std::sync::Arc;
+
+/// [`Policy`] to limit the bytes per second sent by request bodies and received by response bodies.
+///
+/// A [`Body::SeekableStream`] request body is wrapped in a [`ThrottledStream`] once for all tries.
+/// Limits are shared by all clones of this policy.
+#[derive(Debug, Clone)]
+pub struct BandwidthLimitPolicy {
+    options: BandwidthOptions,
+}
+
+impl BandwidthLimitPolicy {
+    pub fn new(options: BandwidthOptions) -> Self {
+        Self { options }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for BandwidthLimitPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let runtime = async_runtime::from_context(ctx);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let (Some(limiter), Body::SeekableStream(stream)) =
+            (&self.options.upload, &mut request.body)
+        {
+            let inner = std::mem::replace(stream, Box::new(BytesStream::new_empty()));
+            *stream = Box::new(ThrottledStream::new(
+                inner,
+                limiter.clone(),
+                runtime.clone(),
+            ));
+        }
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        let Some(limiter) = &self.options.download else {
+            return Ok(response);
+        };
+        let (status, headers, body) = response.deconstruct();
+        let body = body.throttled(limiter.clone(), runtime);
+        Ok(Response::new(status, headers, Box::pin(body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        async_runtime::{AsyncRuntime, RecordingRuntime},
+        http::{headers::Headers, Method, StatusCode},
+        stream::BandwidthLimiter,
+    };
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct Transport;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            Ok(Response::from_bytes(
+                StatusCode::Ok,
+                Headers::new(),
+                "hello world",
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn throttles_response_body() {
+        let sleeps = Arc::new(RecordingRuntime::default());
+        let runtime: Arc<dyn AsyncRuntime> = sleeps.clone();
+        let ctx = Context::new().with_value(runtime);
+        let policy = BandwidthLimitPolicy::new(
+            BandwidthOptions::default().download(BandwidthLimiter::new(10)),
+        );
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+
+        let response = policy
+            .send(&ctx, &mut request, &[Arc::new(Transport)])
+            .await
+            .unwrap();
+        let body = response.into_raw_body().collect().await.unwrap();
+        assert_eq!("hello world", body);
+
+        let sleeps = sleeps.sleeps();
+        assert_eq!(1, sleeps.len());
+        assert!(sleeps[0] > Duration::from_millis(50));
+    }
+}
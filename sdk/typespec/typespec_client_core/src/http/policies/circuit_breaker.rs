@@ -0,0 +1,282 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    date::{clock_from_context, OffsetDateTime},
+    http::{
+        policies::{authority, Policy, PolicyResult},
+        CircuitBreakerOptions, Context, Request,
+    },
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tracing::debug;
+use typespec::error::{Error, ErrorKind};
+
+/// [`Policy`] to stop sending requests to a host after consecutive failures.
+///
+/// A try fails if it returns an [`ErrorKind::Io`] error or a server error status code, and succeeds if it returns
+/// any other status code. Other errors, e.g. [`ErrorKind::Cancelled`], do not change the state of the circuit.
+/// While the circuit for a host is open, requests to it fail with an [`ErrorKind::CircuitOpen`] error,
+/// which is not retried.
+///
+/// The cool-down is measured using the [`Clock`](crate::date::Clock) in the [`Context`], if any.
+/// The state of each circuit is shared by all clones of this policy, and therefore by all clones of a
+/// [`Pipeline`](crate::http::Pipeline) containing it.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerPolicy {
+    options: CircuitBreakerOptions,
+    circuits: Arc<Mutex<HashMap<String, Circuit>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Circuit {
+    /// Requests are sent, counting consecutive failures.
+    Closed { failures: u32 },
+    /// Requests fail immediately until the cool-down has elapsed.
+    Open { until: OffsetDateTime },
+    /// A single request is being sent to test whether the host has recovered.
+    ///
+    /// If the request has not completed by `until`, e.g. because it was dropped, another request is allowed through.
+    HalfOpen { until: OffsetDateTime },
+}
+
+impl CircuitBreakerPolicy {
+    pub fn new(options: CircuitBreakerOptions) -> Self {
+        Self {
+            options,
+            circuits: Arc::default(),
+        }
+    }
+
+    /// Returns `true` if a request to `host` may be sent at `now`.
+    fn try_acquire(&self, host: &str, now: OffsetDateTime) -> bool {
+        let mut circuits = self.circuits.lock().expect("lock poisoned");
+        let Some(circuit) = circuits.get_mut(host) else {
+            return true;
+        };
+        match *circuit {
+            Circuit::Closed { .. } => true,
+            Circuit::Open { until } | Circuit::HalfOpen { until } if until <= now => {
+                debug!("circuit for {host} is half-open");
+                *circuit = Circuit::HalfOpen {
+                    until: now + self.options.cool_down,
+                };
+                true
+            }
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => false,
+        }
+    }
+
+    fn record(&self, host: &str, failed: bool, now: OffsetDateTime) {
+        let mut circuits = self.circuits.lock().expect("lock poisoned");
+        if !failed {
+            circuits.remove(host);
+            return;
+        }
+
+        let circuit = circuits
+            .entry(host.to_string())
+            .or_insert(Circuit::Closed { failures: 0 });
+        let failures = match *circuit {
+            Circuit::Closed { failures } => failures + 1,
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => self.options.failure_threshold,
+        };
+        *circuit = if failures >= self.options.failure_threshold {
+            debug!("circuit for {host} is open after {failures} consecutive failures");
+            Circuit::Open {
+                until: now + self.options.cool_down,
+            }
+        } else {
+            Circuit::Closed { failures }
+        };
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for CircuitBreakerPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let clock = clock_from_context(ctx);
+        let host = authority(request.url());
+        if !self.try_acquire(&host, clock.now()) {
+            return Err(Error::with_message(ErrorKind::CircuitOpen, || {
+                format!("circuit for {host} is open after consecutive failures")
+            }));
+        }
+
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        match &result {
+            Ok(response) => self.record(&host, response.status().is_server_error(), clock.now()),
+            Err(error) if error.kind() == &ErrorKind::Io => self.record(&host, true, clock.now()),
+            Err(_) => {}
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        date::{Clock, ManualClock},
+        http::{headers::Headers, Method, Response, StatusCode},
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    #[derive(Debug, Default)]
+    struct Responder {
+        tries: AtomicUsize,
+        status: Mutex<Option<StatusCode>>,
+    }
+
+    impl Responder {
+        fn respond_with(&self, status: StatusCode) {
+            *self.status.lock().unwrap() = Some(status);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Responder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.tries.fetch_add(1, Ordering::SeqCst);
+            let status = self.status.lock().unwrap().unwrap_or(StatusCode::Ok);
+            Ok(Response::from_bytes(status, Headers::new(), ""))
+        }
+    }
+
+    fn context(clock: &ManualClock) -> Context<'static> {
+        Context::new().with_value::<Arc<dyn Clock>>(Arc::new(clock.clone()))
+    }
+
+    async fn send(
+        policy: &CircuitBreakerPolicy,
+        transport: &Arc<Responder>,
+        clock: &ManualClock,
+        url: &str,
+    ) -> PolicyResult {
+        let transport: Arc<dyn Policy> = transport.clone();
+        let mut request = Request::new(url.parse().unwrap(), Method::Get);
+        policy
+            .send(&context(clock), &mut request, &[transport])
+            .await
+    }
+
+    #[tokio::test]
+    async fn opens_and_half_opens() {
+        let clock = ManualClock::default();
+        let transport = Arc::new(Responder::default());
+        transport.respond_with(StatusCode::ServiceUnavailable);
+        let policy = CircuitBreakerPolicy::new(
+            CircuitBreakerOptions::default()
+                .failure_threshold(2u32)
+                .cool_down(Duration::from_millis(50)),
+        );
+
+        for _ in 0..2 {
+            send(&policy, &transport, &clock, "https://a.example.com")
+                .await
+                .unwrap();
+        }
+        let err = send(&policy, &transport, &clock, "https://a.example.com")
+            .await
+            .unwrap_err();
+        assert_eq!(&ErrorKind::CircuitOpen, err.kind());
+        assert_eq!(2, transport.tries.load(Ordering::SeqCst));
+
+        // Other hosts are not affected, including by clones of the policy.
+        send(&policy.clone(), &transport, &clock, "https://b.example.com")
+            .await
+            .unwrap();
+        assert_eq!(3, transport.tries.load(Ordering::SeqCst));
+
+        // A failed request when half-open opens the circuit again.
+        clock.advance(Duration::from_millis(60));
+        send(&policy, &transport, &clock, "https://a.example.com")
+            .await
+            .unwrap();
+        assert!(
+            send(&policy.clone(), &transport, &clock, "https://a.example.com")
+                .await
+                .is_err()
+        );
+
+        // A successful request when half-open closes the circuit.
+        clock.advance(Duration::from_millis(60));
+        transport.respond_with(StatusCode::Ok);
+        send(&policy, &transport, &clock, "https://a.example.com")
+            .await
+            .unwrap();
+        send(&policy, &transport, &clock, "https://a.example.com")
+            .await
+            .unwrap();
+        assert_eq!(6, transport.tries.load(Ordering::SeqCst));
+    }
+
+    #[derive(Debug)]
+    struct Cancelled;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Cancelled {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            Err(Error::message(ErrorKind::Cancelled, "cancelled"))
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_or_cancelled_probe_does_not_close_circuit() {
+        let clock = ManualClock::default();
+        let transport = Arc::new(Responder::default());
+        transport.respond_with(StatusCode::ServiceUnavailable);
+        let policy = CircuitBreakerPolicy::new(
+            CircuitBreakerOptions::default()
+                .failure_threshold(1u32)
+                .cool_down(Duration::from_millis(50)),
+        );
+        send(&policy, &transport, &clock, "https://a.example.com")
+            .await
+            .unwrap();
+
+        // A probe that is never recorded, e.g. because its future was dropped, is allowed again after the cool-down.
+        clock.advance(Duration::from_millis(60));
+        assert!(policy.try_acquire("a.example.com:443", clock.now()));
+        assert!(!policy.try_acquire("a.example.com:443", clock.now()));
+        clock.advance(Duration::from_millis(60));
+
+        // A cancelled probe leaves the circuit half-open.
+        let cancelled: Arc<dyn Policy> = Arc::new(Cancelled);
+        let mut request = Request::new("https://a.example.com".parse().unwrap(), Method::Get);
+        let err = policy
+            .send(&context(&clock), &mut request, &[cancelled])
+            .await
+            .unwrap_err();
+        assert_eq!(&ErrorKind::Cancelled, err.kind());
+        let err = send(&policy, &transport, &clock, "https://a.example.com")
+            .await
+            .unwrap_err();
+        assert_eq!(&ErrorKind::CircuitOpen, err.kind());
+    }
+}
@@ -0,0 +1,456 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+#[cfg(feature = "gzip")]
+use crate::http::{request::Body, CompressionEncoding, CompressionOptions};
+use crate::{
+    http::{
+        headers::{HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH},
+        policies::{is_range_request, Policy, PolicyResult},
+        response::{PinnedStream, ResponseBody},
+        Context, Method, Request, Response, StatusCode,
+    },
+    Bytes,
+};
+use futures::{
+    io::{AsyncRead, AsyncReadExt},
+    stream, TryStreamExt,
+};
+use std::{io, pin::Pin, sync::Arc};
+use typespec::error::{Error, ErrorKind};
+
+/// The size of each chunk read from a decompressed response body.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// [`Policy`] to compress request bodies.
+///
+/// Request bodies of a known size at least as large as the [`CompressionOptions::threshold`] are compressed
+/// if that makes them smaller, and the `content-encoding` header is set.
+#[cfg(feature = "gzip")]
+#[derive(Clone, Debug)]
+pub struct RequestCompressionPolicy {
+    options: CompressionOptions,
+}
+
+#[cfg(feature = "gzip")]
+impl RequestCompressionPolicy {
+    pub fn new(options: CompressionOptions) -> Self {
+        Self { options }
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RequestCompressionPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if let Body::Bytes(bytes) = request.body() {
+            if bytes.len() >= self.options.threshold
+                && request
+                    .headers()
+                    .get_optional_str(&CONTENT_ENCODING)
+                    .is_none()
+            {
+                let compressed = compress(self.options.encoding, bytes).await?;
+                if compressed.len() < bytes.len() {
+                    request.insert_header(CONTENT_ENCODING, self.options.encoding.as_str());
+                    if request
+                        .headers()
+                        .get_optional_str(&CONTENT_LENGTH)
+                        .is_some()
+                    {
+                        request.insert_header(CONTENT_LENGTH, compressed.len().to_string());
+                    }
+                    request.set_body(compressed);
+                }
+            }
+        }
+
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+#[cfg(feature = "gzip")]
+async fn compress(encoding: CompressionEncoding, bytes: &[u8]) -> crate::Result<Bytes> {
+    use async_compression::futures::bufread::{GzipEncoder, ZlibEncoder};
+
+    let mut compressed = Vec::new();
+    match encoding {
+        CompressionEncoding::Gzip => GzipEncoder::new(bytes).read_to_end(&mut compressed).await,
+        CompressionEncoding::Deflate => ZlibEncoder::new(bytes).read_to_end(&mut compressed).await,
+    }
+    .map_err(|error| Error::full(ErrorKind::Io, error, "failed to compress request body"))?;
+    Ok(compressed.into())
+}
+
+/// [`Policy`] to decompress response bodies.
+///
+/// The `accept-encoding` header is set to the encodings enabled by crate features unless the request already has one.
+/// A response body with a supported `content-encoding` is decompressed as it is read, and the `content-encoding`
+/// and `content-length` headers are removed.
+///
+/// Ranged requests, which have a `range` or `x-ms-range` header, are sent and returned as is because part of a
+/// compressed body cannot be decompressed. Responses to `HEAD` requests and responses without a body,
+/// e.g. `304 Not Modified`, are also returned as is so their headers describe the resource.
+///
+/// | Feature  | Encodings         |
+/// |----------|-------------------|
+/// | `gzip`   | `gzip`, `deflate` |
+/// | `brotli` | `br`              |
+/// | `zstd`   | `zstd`            |
+#[derive(Clone, Debug, Default)]
+pub struct DecompressionPolicy {}
+
+impl DecompressionPolicy {
+    /// The value of the `accept-encoding` header for the enabled encodings.
+    const ACCEPT_ENCODING: &'static str = {
+        #[cfg(all(feature = "gzip", feature = "brotli", feature = "zstd"))]
+        let value = "gzip, deflate, br, zstd";
+        #[cfg(all(feature = "gzip", feature = "brotli", not(feature = "zstd")))]
+        let value = "gzip, deflate, br";
+        #[cfg(all(feature = "gzip", not(feature = "brotli"), feature = "zstd"))]
+        let value = "gzip, deflate, zstd";
+        #[cfg(all(feature = "gzip", not(feature = "brotli"), not(feature = "zstd")))]
+        let value = "gzip, deflate";
+        #[cfg(all(not(feature = "gzip"), feature = "brotli", feature = "zstd"))]
+        let value = "br, zstd";
+        #[cfg(all(not(feature = "gzip"), feature = "brotli", not(feature = "zstd")))]
+        let value = "br";
+        #[cfg(all(not(feature = "gzip"), not(feature = "brotli")))]
+        let value = "zstd";
+        value
+    };
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for DecompressionPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if is_range_request(request) {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+        if request
+            .headers()
+            .get_optional_str(&ACCEPT_ENCODING)
+            .is_none()
+        {
+            request.insert_header(
+                ACCEPT_ENCODING,
+                HeaderValue::from_static(Self::ACCEPT_ENCODING),
+            );
+        }
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        if *request.method() == Method::Head || !has_body(response.status()) {
+            return Ok(response);
+        }
+        let Some(encoding) = response
+            .headers()
+            .get_optional_str(&CONTENT_ENCODING)
+            .map(|encoding| encoding.trim().to_ascii_lowercase())
+        else {
+            return Ok(response);
+        };
+        if !is_supported(&encoding) {
+            return Ok(response);
+        }
+
+        let (status, mut headers, body) = response.deconstruct();
+        headers.remove(&CONTENT_ENCODING);
+        headers.remove(&CONTENT_LENGTH);
+        Ok(Response::new(status, headers, decode(&encoding, body)))
+    }
+}
+
+/// Returns `true` if a response with `status` may have a body.
+fn has_body(status: StatusCode) -> bool {
+    !matches!(
+        status,
+        StatusCode::Continue
+            | StatusCode::SwitchingProtocols
+            | StatusCode::NoContent
+            | StatusCode::NotModified
+    )
+}
+
+fn is_supported(encoding: &str) -> bool {
+    match encoding {
+        #[cfg(feature = "gzip")]
+        "gzip" | "x-gzip" | "deflate" => true,
+        #[cfg(feature = "brotli")]
+        "br" => true,
+        #[cfg(feature = "zstd")]
+        "zstd" => true,
+        _ => false,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type Decoder = Pin<Box<dyn AsyncRead + Send + Sync>>;
+#[cfg(target_arch = "wasm32")]
+type Decoder = Pin<Box<dyn AsyncRead>>;
+
+/// Decodes `body` with a supported `encoding`.
+fn decode(encoding: &str, body: ResponseBody) -> PinnedStream {
+    #[cfg(feature = "brotli")]
+    use async_compression::futures::bufread::BrotliDecoder;
+    #[cfg(feature = "zstd")]
+    use async_compression::futures::bufread::ZstdDecoder;
+    #[cfg(feature = "gzip")]
+    use async_compression::futures::bufread::{GzipDecoder, ZlibDecoder};
+
+    let reader = body.map_err(io::Error::other).into_async_read();
+    let decoder: Decoder = match encoding {
+        #[cfg(feature = "gzip")]
+        "gzip" | "x-gzip" => Box::pin(GzipDecoder::new(reader)),
+        #[cfg(feature = "gzip")]
+        "deflate" => Box::pin(ZlibDecoder::new(reader)),
+        #[cfg(feature = "brotli")]
+        "br" => Box::pin(BrotliDecoder::new(reader)),
+        #[cfg(feature = "zstd")]
+        "zstd" => Box::pin(ZstdDecoder::new(reader)),
+        _ => unreachable!("encoding is supported"),
+    };
+
+    Box::pin(stream::unfold(Some(decoder), |decoder| async move {
+        let mut decoder = decoder?;
+        let mut buffer = vec![0; CHUNK_SIZE];
+        match decoder.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(read) => {
+                buffer.truncate(read);
+                Some((Ok(Bytes::from(buffer)), Some(decoder)))
+            }
+            Err(error) => Some((
+                Err(Error::full(
+                    ErrorKind::Io,
+                    error,
+                    "failed to decompress response body",
+                )),
+                None,
+            )),
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{headers::Headers, Method, StatusCode};
+    use std::sync::Mutex;
+
+    /// Responds with the encoded `body` and records the request.
+    #[derive(Debug, Default)]
+    struct Responder {
+        status: Option<StatusCode>,
+        encoding: Option<&'static str>,
+        body: Vec<u8>,
+        request: Mutex<Option<Request>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Responder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            *self.request.lock().unwrap() = Some(request.clone());
+            let mut headers = Headers::new();
+            if let Some(encoding) = self.encoding {
+                headers.insert(CONTENT_ENCODING, encoding);
+            }
+            headers.insert(CONTENT_LENGTH, self.body.len().to_string());
+            Ok(Response::from_bytes(
+                self.status.unwrap_or(StatusCode::Ok),
+                headers,
+                self.body.clone(),
+            ))
+        }
+    }
+
+    async fn decompress(encoding: &'static str, body: Vec<u8>) -> (Option<String>, Bytes) {
+        let transport = Arc::new(Responder {
+            encoding: Some(encoding),
+            body,
+            ..Default::default()
+        });
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let response = DecompressionPolicy::default()
+            .send(&Context::new(), &mut request, &[transport.clone()])
+            .await
+            .unwrap();
+        assert!(response
+            .headers()
+            .get_optional_str(&CONTENT_ENCODING)
+            .is_none());
+        assert!(response
+            .headers()
+            .get_optional_str(&CONTENT_LENGTH)
+            .is_none());
+
+        let accept_encoding = transport
+            .request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .headers()
+            .get_optional_string(&ACCEPT_ENCODING);
+        let body = response.into_raw_body().collect().await.unwrap();
+        (accept_encoding, body)
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn compresses_request_body() {
+        let transport = Arc::new(Responder::default());
+        let policy = RequestCompressionPolicy::new(
+            CompressionOptions::default()
+                .encoding(CompressionEncoding::Deflate)
+                .threshold(16usize),
+        );
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let body = "hello ".repeat(100);
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Post);
+        request.set_body(body.clone());
+        policy
+            .send(&Context::new(), &mut request, &next)
+            .await
+            .unwrap();
+
+        let sent = transport.request.lock().unwrap().take().unwrap();
+        assert_eq!(
+            Some("deflate"),
+            sent.headers().get_optional_str(&CONTENT_ENCODING)
+        );
+        let Body::Bytes(compressed) = sent.body() else {
+            panic!("expected bytes");
+        };
+        assert!(compressed.len() < body.len());
+        let mut decompressed = String::new();
+        async_compression::futures::bufread::ZlibDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .await
+            .unwrap();
+        assert_eq!(body, decompressed);
+
+        // Bodies smaller than the threshold are sent as is.
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Post);
+        request.set_body("small");
+        policy
+            .send(&Context::new(), &mut request, &next)
+            .await
+            .unwrap();
+        let sent = transport.request.lock().unwrap().take().unwrap();
+        assert!(sent.headers().get_optional_str(&CONTENT_ENCODING).is_none());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn decompresses_gzip_response() {
+        let compressed = compress(CompressionEncoding::Gzip, b"hello world")
+            .await
+            .unwrap();
+        let (accept_encoding, body) = decompress("gzip", compressed.to_vec()).await;
+        assert!(accept_encoding.unwrap().starts_with("gzip, deflate"));
+        assert_eq!(Bytes::from_static(b"hello world"), body);
+    }
+
+    #[cfg(feature = "brotli")]
+    #[tokio::test]
+    async fn decompresses_brotli_response() {
+        let mut compressed = Vec::new();
+        async_compression::futures::bufread::BrotliEncoder::new(&b"hello world"[..])
+            .read_to_end(&mut compressed)
+            .await
+            .unwrap();
+        let (accept_encoding, body) = decompress("br", compressed).await;
+        assert!(accept_encoding.unwrap().contains("br"));
+        assert_eq!(Bytes::from_static(b"hello world"), body);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[tokio::test]
+    async fn decompresses_zstd_response() {
+        let mut compressed = Vec::new();
+        async_compression::futures::bufread::ZstdEncoder::new(&b"hello world"[..])
+            .read_to_end(&mut compressed)
+            .await
+            .unwrap();
+        let (accept_encoding, body) = decompress("zstd", compressed).await;
+        assert!(accept_encoding.unwrap().contains("zstd"));
+        assert_eq!(Bytes::from_static(b"hello world"), body);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn skips_head_and_bodyless_responses() {
+        for (method, status) in [
+            (Method::Head, StatusCode::Ok),
+            (Method::Get, StatusCode::NoContent),
+            (Method::Get, StatusCode::NotModified),
+        ] {
+            let transport = Arc::new(Responder {
+                status: Some(status),
+                encoding: Some("gzip"),
+                ..Default::default()
+            });
+            let mut request = Request::new("http://localhost".parse().unwrap(), method);
+            let response = DecompressionPolicy::default()
+                .send(&Context::new(), &mut request, &[transport])
+                .await
+                .unwrap();
+            assert_eq!(
+                Some("gzip"),
+                response.headers().get_optional_str(&CONTENT_ENCODING)
+            );
+            assert_eq!(
+                Some("0"),
+                response.headers().get_optional_str(&CONTENT_LENGTH)
+            );
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn skips_ranged_requests() {
+        use crate::http::headers::{MS_RANGE, RANGE};
+
+        for (name, value) in [(RANGE, "bytes=0-9"), (MS_RANGE, "bytes=0-9")] {
+            let transport = Arc::new(Responder {
+                encoding: Some("gzip"),
+                body: b"partial".to_vec(),
+                ..Default::default()
+            });
+            let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+            request.insert_header(name, value);
+            let response = DecompressionPolicy::default()
+                .send(&Context::new(), &mut request, &[transport.clone()])
+                .await
+                .unwrap();
+
+            let sent = transport.request.lock().unwrap().take().unwrap();
+            assert!(sent.headers().get_optional_str(&ACCEPT_ENCODING).is_none());
+            assert_eq!(
+                Some("gzip"),
+                response.headers().get_optional_str(&CONTENT_ENCODING)
+            );
+            let body = response.into_raw_body().collect().await.unwrap();
+            assert_eq!(Bytes::from_static(b"partial"), body);
+        }
+    }
+}
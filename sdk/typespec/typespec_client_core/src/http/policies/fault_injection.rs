@@ -0,0 +1,421 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    async_runtime,
+    error::{Error, ErrorKind},
+    http::{
+        headers::{Headers, RETRY_AFTER, RETRY_AFTER_MS},
+        policies::{Policy, PolicyResult, TransportPolicy, TryNumber},
+        Cancellation, Context, Method, Request, Response, StatusCode, TransportOptions,
+    },
+};
+use bytes::Bytes;
+use futures::stream;
+use std::{io, ops::RangeInclusive, sync::Arc, time::Duration};
+use tracing::debug;
+
+/// A fault injected by the [`FaultInjectionPolicy`].
+#[derive(Clone, Debug)]
+pub enum Fault {
+    /// Returns a response without sending the request.
+    Status {
+        /// The status of the response.
+        status: StatusCode,
+
+        /// The headers of the response.
+        headers: Headers,
+
+        /// The body of the response.
+        body: Bytes,
+    },
+
+    /// Returns an [`ErrorKind::Io`] error as if the connection was reset, without sending the request.
+    ConnectionError,
+
+    /// Waits before sending the request.
+    ///
+    /// The wait only counts toward [`ClientOptions::per_try_timeout`](crate::http::ClientOptions::per_try_timeout)
+    /// if the policy is used as the transport; see [`FaultInjectionPolicy::with_transport`].
+    Latency(Duration),
+
+    /// Sends the request and ends the response body after the given number of bytes.
+    TruncatedBody(usize),
+
+    /// Sends the request and returns an [`ErrorKind::Io`] error from the response body after the given number of bytes.
+    BodyError(usize),
+}
+
+impl Fault {
+    /// Creates a [`Fault::Status`] response with `status` and no headers or body.
+    pub fn status(status: StatusCode) -> Self {
+        Self::Status {
+            status,
+            headers: Headers::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// Creates a [`Fault::Status`] response with `status` and a `retry-after` header.
+    ///
+    /// The `retry-after-ms` header is used instead if `retry_after` is not a whole number of seconds.
+    pub fn status_with_retry_after(status: StatusCode, retry_after: Duration) -> Self {
+        let mut headers = Headers::new();
+        if retry_after.subsec_nanos() == 0 {
+            headers.insert(RETRY_AFTER, retry_after.as_secs().to_string());
+        } else {
+            headers.insert(RETRY_AFTER_MS, retry_after.as_millis().to_string());
+        }
+        Self::Status {
+            status,
+            headers,
+            body: Bytes::new(),
+        }
+    }
+}
+
+/// A rule that injects a [`Fault`] into matching requests.
+///
+/// A rule without a method, URL, or tries matches every try of every request.
+#[derive(Clone, Debug)]
+pub struct FaultRule {
+    /// The fault to inject.
+    pub fault: Fault,
+
+    /// Matches only requests with this method.
+    ///
+    /// The default is `None`, which matches any method.
+    pub method: Option<Method>,
+
+    /// Matches only requests with a URL that matches this pattern, in which `*` matches any characters.
+    ///
+    /// The default is `None`, which matches any URL.
+    pub url: Option<String>,
+
+    /// Matches only these tries of a request, starting at 1.
+    ///
+    /// The default is `None`, which matches every try.
+    pub tries: Option<RangeInclusive<u32>>,
+}
+
+impl FaultRule {
+    /// Creates a `FaultRule` that injects `fault` into every try of every request.
+    pub fn new(fault: Fault) -> Self {
+        Self {
+            fault,
+            method: None,
+            url: None,
+            tries: None,
+        }
+    }
+
+    setters! {
+        method: Method => Some(method),
+        url: String => Some(url),
+        tries: RangeInclusive<u32> => Some(tries),
+    }
+
+    fn matches(&self, request: &Request, try_number: u32) -> bool {
+        self.method
+            .as_ref()
+            .map_or(true, |method| method == request.method())
+            && self.url.as_ref().map_or(true, |pattern| {
+                matches_pattern(pattern, request.url().as_str())
+            })
+            && self
+                .tries
+                .as_ref()
+                .map_or(true, |tries| tries.contains(&try_number))
+    }
+}
+
+/// [`Policy`] to inject faults into requests for testing resilience.
+///
+/// Each try uses the first [`FaultRule`] that matches it, if any. Add this policy to
+/// [`ClientOptions::per_try_policies`](crate::http::ClientOptions::per_try_policies) so that faults are retried
+/// by the retry policy and rules can match the try number. To inject faults after the per-try timeout, e.g. so that
+/// [`Fault::Latency`] causes a try to time out, use the policy as the transport with [`FaultInjectionPolicy::with_transport`].
+///
+/// # Example
+///
+/// Failing the first try of each `GET` request with `503 Service Unavailable` and the second try with a connection error.
+/// ```
+/// # use std::{sync::Arc, time::Duration};
+/// # use typespec_client_core::http::{policies::{Fault, FaultInjectionPolicy, FaultRule}, ClientOptions, Method, StatusCode};
+/// let policy = FaultInjectionPolicy::new(vec![
+///     FaultRule::new(Fault::status_with_retry_after(StatusCode::ServiceUnavailable, Duration::from_secs(1)))
+///         .method(Method::Get)
+///         .tries(1..=1),
+///     FaultRule::new(Fault::ConnectionError).method(Method::Get).tries(2..=2),
+/// ]);
+/// let options = ClientOptions {
+///     per_try_policies: vec![Arc::new(policy)],
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Delaying the first try of each request by 10 seconds so that it times out.
+/// ```
+/// # use std::{sync::Arc, time::Duration};
+/// # use typespec_client_core::http::{policies::{Fault, FaultInjectionPolicy, FaultRule}, ClientOptions, TransportOptions};
+/// let policy = FaultInjectionPolicy::new(vec![
+///     FaultRule::new(Fault::Latency(Duration::from_secs(10))).tries(1..=1),
+/// ])
+/// .with_transport(TransportOptions::default());
+/// let options = ClientOptions {
+///     per_try_timeout: Some(Duration::from_secs(5)),
+///     transport: Some(TransportOptions::new_custom_policy(Arc::new(policy))),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct FaultInjectionPolicy {
+    rules: Vec<FaultRule>,
+    transport: Option<Arc<dyn Policy>>,
+}
+
+impl FaultInjectionPolicy {
+    pub fn new(rules: Vec<FaultRule>) -> Self {
+        Self {
+            rules,
+            transport: None,
+        }
+    }
+
+    /// Sends requests with the `transport` so that this policy can be used as the transport of a pipeline.
+    ///
+    /// Set [`ClientOptions::transport`](crate::http::ClientOptions::transport) to a
+    /// [`TransportOptions::new_custom_policy`] with this policy to inject faults after all other policies, including the per-try timeout.
+    pub fn with_transport(mut self, transport: TransportOptions) -> Self {
+        self.transport = Some(Arc::new(TransportPolicy::new(transport)));
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for FaultInjectionPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let transport: [Arc<dyn Policy>; 1];
+        let next = match &self.transport {
+            Some(policy) => {
+                transport = [policy.clone()];
+                &transport[..]
+            }
+            None => next,
+        };
+        let try_number = ctx
+            .value::<TryNumber>()
+            .map_or(1, |try_number| try_number.0);
+        let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(request, try_number))
+        else {
+            return next[0].send(ctx, request, &next[1..]).await;
+        };
+        debug!(
+            "injecting fault {:?} into try {try_number} of {} {}",
+            rule.fault,
+            request.method(),
+            request.url()
+        );
+
+        let (keep, error) = match &rule.fault {
+            Fault::Status {
+                status,
+                headers,
+                body,
+            } => {
+                return Ok(Response::from_bytes(*status, headers.clone(), body.clone()));
+            }
+            Fault::ConnectionError => return Err(injected_error()),
+            Fault::Latency(delay) => {
+                let sleep = async_runtime::from_context(ctx).sleep(*delay);
+                Cancellation::from_context(ctx)
+                    .run(async {
+                        sleep.await;
+                        Ok(())
+                    })
+                    .await?;
+                return next[0].send(ctx, request, &next[1..]).await;
+            }
+            Fault::TruncatedBody(keep) => (*keep, false),
+            Fault::BodyError(keep) => (*keep, true),
+        };
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        let (status, headers, body) = response.deconstruct();
+        let mut body = body.collect().await?;
+        body.truncate(keep);
+        let error = error.then(|| Err(injected_error()));
+        let chunks = stream::iter([Ok(body)].into_iter().chain(error));
+        Ok(Response::new(status, headers, Box::pin(chunks)))
+    }
+}
+
+fn injected_error() -> Error {
+    Error::new(
+        ErrorKind::Io,
+        io::Error::new(io::ErrorKind::ConnectionReset, "injected fault"),
+    )
+}
+
+/// Returns whether `text` matches `pattern`, in which `*` matches any characters.
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // The pattern has no wildcards.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{ClientOptions, FixedRetryOptions, Pipeline, RetryOptions};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Transport {
+        requests: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            Ok(Response::from_bytes(
+                StatusCode::Ok,
+                Headers::new(),
+                "hello world",
+            ))
+        }
+    }
+
+    fn pipeline(rules: Vec<FaultRule>, transport: Arc<Transport>) -> Vec<Arc<dyn Policy>> {
+        let retry = RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(1))
+                .max_retries(3u32),
+        )
+        .to_policy();
+        vec![retry, Arc::new(FaultInjectionPolicy::new(rules)), transport]
+    }
+
+    #[test]
+    fn matches_url_patterns() {
+        assert!(matches_pattern(
+            "https://*/secrets/*",
+            "https://vault/secrets/a"
+        ));
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("https://vault/", "https://vault/"));
+        assert!(matches_pattern("*/a*a", "x/aba"));
+        assert!(!matches_pattern("*/a*a", "x/a"));
+        assert!(!matches_pattern(
+            "https://*/keys/*",
+            "https://vault/secrets/a"
+        ));
+    }
+
+    #[tokio::test]
+    async fn injects_faults_by_try() {
+        let transport = Arc::new(Transport::default());
+        let next = pipeline(
+            vec![
+                FaultRule::new(Fault::ConnectionError).method(Method::Post),
+                FaultRule::new(Fault::status_with_retry_after(
+                    StatusCode::ServiceUnavailable,
+                    Duration::from_millis(1),
+                ))
+                .url("*/secrets/*")
+                .tries(1..=1),
+                FaultRule::new(Fault::ConnectionError).tries(2..=2),
+                FaultRule::new(Fault::TruncatedBody(5)).tries(3..=3),
+            ],
+            transport.clone(),
+        );
+
+        let mut request = Request::new("http://localhost/secrets/a".parse().unwrap(), Method::Get);
+        let response = next[0]
+            .send(&Context::new(), &mut request, &next[1..])
+            .await
+            .unwrap();
+        let body = response.into_raw_body().collect().await.unwrap();
+        assert_eq!("hello", body);
+        assert_eq!(1, transport.requests.load(Ordering::SeqCst));
+
+        // Every try fails with a connection error.
+        let mut request = Request::new("http://localhost/secrets/a".parse().unwrap(), Method::Post);
+        let err = next[0]
+            .send(&Context::new(), &mut request, &next[1..])
+            .await
+            .unwrap_err();
+        assert_eq!(ErrorKind::Io, *err.kind());
+        assert_eq!(1, transport.requests.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn injects_body_errors() {
+        let transport = Arc::new(Transport::default());
+        let next = pipeline(vec![FaultRule::new(Fault::BodyError(3))], transport.clone());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let response = next[0]
+            .send(&Context::new(), &mut request, &next[1..])
+            .await
+            .unwrap();
+        let err = response.into_raw_body().collect().await.unwrap_err();
+        assert_eq!(ErrorKind::Io, *err.kind());
+    }
+
+    #[tokio::test]
+    async fn latency_as_transport_times_out() {
+        let transport = Arc::new(Transport::default());
+        let policy = FaultInjectionPolicy::new(vec![FaultRule::new(Fault::Latency(
+            Duration::from_secs(60),
+        ))
+        .tries(1..=1)])
+        .with_transport(TransportOptions::new_custom_policy(transport.clone()));
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default().delay(Duration::from_millis(1)),
+            )),
+            per_try_timeout: Some(Duration::from_millis(50)),
+            transport: Some(TransportOptions::new_custom_policy(Arc::new(policy))),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline
+            .send::<()>(&Context::new(), &mut request)
+            .await
+            .unwrap();
+        assert_eq!(1, transport.requests.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    date::{self, OffsetDateTime},
+    http::{
+        headers::CONTENT_LENGTH,
+        policies::{Policy, PolicyResult},
+        Context, LoggingOptions, Request,
+    },
+};
+use std::sync::Arc;
+use tracing::{debug, Level};
+
+/// [`Policy`] to log each request and response.
+///
+/// Requests and responses are logged at the `DEBUG` level. Header and query parameter values
+/// are redacted unless allowed by the [`LoggingOptions`].
+#[derive(Debug, Clone)]
+pub struct LoggingPolicy {
+    options: LoggingOptions,
+}
+
+impl LoggingPolicy {
+    pub fn new(options: LoggingOptions) -> Self {
+        Self { options }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for LoggingPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if !tracing::enabled!(Level::DEBUG) {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+
+        let method = *request.method();
+        let url = self.options.sanitize_url(request.url());
+        let body_size = if self.options.log_body_size {
+            format!(", body size: {}", request.body().len())
+        } else {
+            String::new()
+        };
+        debug!(
+            "sending request {method} {url}, headers: {}{body_size}",
+            self.options.sanitize_headers(request.headers())
+        );
+
+        let start = OffsetDateTime::now_utc();
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        let duration = date::diff(OffsetDateTime::now_utc(), start);
+
+        match &result {
+            Ok(response) => {
+                let body_size = match response.headers().get_optional_str(&CONTENT_LENGTH) {
+                    Some(content_length) if self.options.log_body_size => {
+                        format!(", body size: {content_length}")
+                    }
+                    _ => String::new(),
+                };
+                debug!(
+                    "received response {} for {method} {url} after {duration:?}, headers: {}{body_size}",
+                    response.status(),
+                    self.options.sanitize_headers(response.headers())
+                );
+            }
+            Err(error) => {
+                debug!("request {method} {url} failed after {duration:?}: {error}");
+            }
+        }
+
+        result
+    }
+}
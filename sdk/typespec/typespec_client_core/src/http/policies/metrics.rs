@@ -0,0 +1,258 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    error::ErrorKind,
+    http::{
+        headers::CONTENT_LENGTH,
+        policies::{Policy, PolicyResult},
+        Context, Meter, MetricsOptions, OperationName, Request, CLIENT_LABEL, HOST_LABEL,
+        HTTP_CLIENT_ERRORS, HTTP_CLIENT_REQUESTS, HTTP_CLIENT_REQUEST_DURATION,
+        HTTP_CLIENT_RESPONSE_BODY_SIZE, HTTP_CLIENT_RETRIES, OPERATION_LABEL, STATUS_LABEL,
+    },
+};
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+/// The number of tries of a call, counted by the [`RequestMetricsPolicy`].
+#[derive(Debug, Default)]
+struct CallTries(AtomicU32);
+
+/// [`Policy`] to record metrics for each logical operation.
+///
+/// This policy is called once per call before the retry policy and also counts the retries of each call.
+/// The metrics recorded are named by the `HTTP_CLIENT_*` constants in [`http`](crate::http).
+#[derive(Debug, Clone)]
+pub struct MetricsPolicy {
+    meter: Arc<dyn Meter>,
+    client_name: String,
+}
+
+impl MetricsPolicy {
+    pub fn new(options: MetricsOptions) -> Self {
+        Self {
+            meter: options.meter,
+            client_name: options.client_name.unwrap_or_default().into_owned(),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for MetricsPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        // Monotonic time is not available on wasm32 so the duration is not recorded there.
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = std::time::Instant::now();
+        let ctx = Context::with_context(ctx).with_value(CallTries::default());
+        let result = next[0].send(&ctx, request, &next[1..]).await;
+
+        let status = match &result {
+            Ok(response) => Some(response.status()),
+            Err(error) => match error.kind() {
+                ErrorKind::HttpResponse { status, .. } => Some(*status),
+                _ => None,
+            },
+        };
+        let labels = [
+            (CLIENT_LABEL, self.client_name.clone()),
+            (
+                OPERATION_LABEL,
+                ctx.value::<OperationName>().map_or_else(
+                    || request.method().to_string(),
+                    |name| name.as_str().to_string(),
+                ),
+            ),
+            (
+                HOST_LABEL,
+                request.url().host_str().unwrap_or_default().to_string(),
+            ),
+            (
+                STATUS_LABEL,
+                status.map_or_else(|| "none".to_string(), |s| u16::from(s).to_string()),
+            ),
+        ];
+
+        self.meter.add_to_counter(HTTP_CLIENT_REQUESTS, 1, &labels);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.meter.record_histogram(
+            HTTP_CLIENT_REQUEST_DURATION,
+            start.elapsed().as_secs_f64(),
+            &labels,
+        );
+        let tries = ctx
+            .value::<CallTries>()
+            .map_or(0, |tries| tries.0.load(Ordering::Acquire));
+        if tries > 1 {
+            self.meter
+                .add_to_counter(HTTP_CLIENT_RETRIES, u64::from(tries - 1), &labels);
+        }
+        match &result {
+            Ok(response) if response.status().is_success() => {
+                let size = response
+                    .headers()
+                    .get_optional_str(&CONTENT_LENGTH)
+                    .and_then(|length| length.parse::<u64>().ok());
+                if let Some(size) = size {
+                    self.meter.record_histogram(
+                        HTTP_CLIENT_RESPONSE_BODY_SIZE,
+                        size as f64,
+                        &labels,
+                    );
+                }
+            }
+            _ => self.meter.add_to_counter(HTTP_CLIENT_ERRORS, 1, &labels),
+        }
+
+        result
+    }
+}
+
+/// [`Policy`] to count the tries of each call for the [`MetricsPolicy`].
+///
+/// This policy is called once per try after the retry policy.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestMetricsPolicy;
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RequestMetricsPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if let Some(tries) = ctx.value::<CallTries>() {
+            tries.0.fetch_add(1, Ordering::AcqRel);
+        }
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        headers::Headers, FixedRetryOptions, Method, MetricLabels, Response, RetryOptions,
+        StatusCode,
+    };
+    use std::{sync::Mutex, time::Duration};
+
+    type Metric = (&'static str, f64, Vec<(&'static str, String)>);
+
+    #[derive(Debug, Default)]
+    struct RecordingMeter {
+        metrics: Mutex<Vec<Metric>>,
+    }
+
+    impl RecordingMeter {
+        fn value(&self, name: &str) -> Option<f64> {
+            let metrics = self.metrics.lock().unwrap();
+            metrics.iter().find(|m| m.0 == name).map(|m| m.1)
+        }
+    }
+
+    impl Meter for RecordingMeter {
+        fn add_to_counter(&self, name: &'static str, value: u64, labels: MetricLabels<'_>) {
+            self.record_histogram(name, value as f64, labels);
+        }
+
+        fn record_histogram(&self, name: &'static str, value: f64, labels: MetricLabels<'_>) {
+            self.metrics
+                .lock()
+                .unwrap()
+                .push((name, value, labels.to_vec()));
+        }
+    }
+
+    /// Returns `503 Service Unavailable` to the first try and `status` with a 5 byte body to later tries.
+    #[derive(Debug)]
+    struct Transport {
+        tries: AtomicU32,
+        status: StatusCode,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            if self.tries.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Ok(Response::from_bytes(
+                    StatusCode::ServiceUnavailable,
+                    Headers::new(),
+                    "",
+                ));
+            }
+            let mut headers = Headers::new();
+            headers.insert(CONTENT_LENGTH, "5");
+            Ok(Response::from_bytes(self.status, headers, "hello"))
+        }
+    }
+
+    async fn send(status: StatusCode) -> Arc<RecordingMeter> {
+        let meter = Arc::new(RecordingMeter::default());
+        let retry = RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(1))
+                .max_retries(1u32),
+        )
+        .to_policy();
+        let policy = MetricsPolicy::new(MetricsOptions::new(meter.clone()).client_name("tests"));
+        let next: Vec<Arc<dyn Policy>> = vec![
+            retry,
+            Arc::new(RequestMetricsPolicy),
+            Arc::new(Transport {
+                tries: AtomicU32::new(0),
+                status,
+            }),
+        ];
+        let ctx = Context::new().with_value(OperationName::from("get_secret"));
+        let mut request = Request::new("https://vault/secrets/a".parse().unwrap(), Method::Get);
+        let _ = policy.send(&ctx, &mut request, &next).await;
+        meter
+    }
+
+    #[tokio::test]
+    async fn records_successful_calls() {
+        let meter = send(StatusCode::Ok).await;
+        assert_eq!(Some(1.0), meter.value(HTTP_CLIENT_REQUESTS));
+        assert_eq!(Some(1.0), meter.value(HTTP_CLIENT_RETRIES));
+        assert_eq!(Some(5.0), meter.value(HTTP_CLIENT_RESPONSE_BODY_SIZE));
+        assert!(meter.value(HTTP_CLIENT_REQUEST_DURATION).is_some());
+        assert_eq!(None, meter.value(HTTP_CLIENT_ERRORS));
+
+        let metrics = meter.metrics.lock().unwrap();
+        assert_eq!(
+            vec![
+                (CLIENT_LABEL, "tests".to_string()),
+                (OPERATION_LABEL, "get_secret".to_string()),
+                (HOST_LABEL, "vault".to_string()),
+                (STATUS_LABEL, "200".to_string()),
+            ],
+            metrics[0].2
+        );
+    }
+
+    #[tokio::test]
+    async fn records_errors() {
+        let meter = send(StatusCode::NotFound).await;
+        assert_eq!(Some(1.0), meter.value(HTTP_CLIENT_ERRORS));
+        assert_eq!(None, meter.value(HTTP_CLIENT_RESPONSE_BODY_SIZE));
+
+        let metrics = meter.metrics.lock().unwrap();
+        assert!(metrics[0].2.contains(&(STATUS_LABEL, "404".to_string())));
+    }
+}
@@ -3,16 +3,45 @@
 
 //! HTTP pipeline policies.
 
-use crate::http::{Context, Request, Response};
+use crate::http::{
+    headers::{MS_RANGE, RANGE},
+    Context, Request, Response, Url,
+};
 use async_trait::async_trait;
 use std::sync::Arc;
 
+mod bandwidth;
+mod circuit_breaker;
+#[cfg(any(feature = "gzip", feature = "brotli", feature = "zstd"))]
+mod compression;
 mod custom_headers;
+#[cfg(feature = "distributed_tracing")]
+mod distributed_tracing;
+mod fault_injection;
+mod logging;
+mod metrics;
+mod progress;
+mod rate_limit;
+mod response_cache;
 mod retry;
+mod timeout;
 mod transport;
 
+pub use bandwidth::*;
+pub use circuit_breaker::*;
+#[cfg(any(feature = "gzip", feature = "brotli", feature = "zstd"))]
+pub use compression::*;
 pub use custom_headers::*;
+#[cfg(feature = "distributed_tracing")]
+pub use distributed_tracing::*;
+pub use fault_injection::*;
+pub use logging::*;
+pub use metrics::*;
+pub use progress::*;
+pub use rate_limit::*;
+pub use response_cache::*;
 pub use retry::*;
+pub use timeout::*;
 pub use transport::*;
 
 /// A specialized `Result` type for policies.
@@ -34,3 +63,18 @@ pub trait Policy: Send + Sync + std::fmt::Debug {
         next: &[Arc<dyn Policy>],
     ) -> PolicyResult;
 }
+
+/// Gets the host and port of the `url` to key per-host state.
+pub(crate) fn authority(url: &Url) -> String {
+    match (url.host_str(), url.port_or_known_default()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => host.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Returns `true` if the `request` asks for part of a resource with a `range` or `x-ms-range` header.
+pub(crate) fn is_range_request(request: &Request) -> bool {
+    request.headers().get_optional_str(&RANGE).is_some()
+        || request.headers().get_optional_str(&MS_RANGE).is_some()
+}
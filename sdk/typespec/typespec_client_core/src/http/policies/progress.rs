@@ -0,0 +1,152 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    http::Body,
+    stream::{BytesStream, ProgressStream},
+};
+use crate::{
+    http::{
+        headers::CONTENT_LENGTH,
+        policies::{Policy, PolicyResult},
+        Context, Request, Response,
+    },
+    stream::ProgressCallback,
+};
+use std::sync::Arc;
+
+/// Reports upload and download progress to the [`ProgressCallback`] in the [`Context`], if any.
+///
+/// A [`Body::SeekableStream`] request body is wrapped in a [`ProgressStream`] once for all tries,
+/// so progress is reported again from zero when the body is reset to retry the request.
+/// The response body reports the bytes received as it is consumed.
+#[derive(Debug, Clone, Default)]
+pub struct TransferProgressPolicy;
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for TransferProgressPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let Some(callback) = ctx.value::<ProgressCallback>().cloned() else {
+            return next[0].send(ctx, request, &next[1..]).await;
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Body::SeekableStream(stream) = &mut request.body {
+            let inner = std::mem::replace(stream, Box::new(BytesStream::new_empty()));
+            *stream = Box::new(ProgressStream::new(inner, callback.clone()));
+        }
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        let (status, headers, body) = response.deconstruct();
+        let total_bytes = headers
+            .get_optional_str(&CONTENT_LENGTH)
+            .and_then(|length| length.parse().ok());
+        let body = body.with_progress(callback, total_bytes);
+        Ok(Response::new(status, headers, Box::pin(body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{headers::Headers, FixedRetryOptions, Method, RetryOptions, StatusCode},
+        stream::{SeekableStream, TransferDirection, TransferProgress},
+    };
+    use futures::StreamExt;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+        time::Duration,
+    };
+
+    /// Reads the request body and fails the first try with a 503.
+    #[derive(Debug, Default)]
+    struct Transport {
+        tries: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            if let Body::SeekableStream(stream) = request.body().clone() {
+                let mut stream: Box<dyn SeekableStream> = stream;
+                while let Some(chunk) = stream.next().await {
+                    chunk?;
+                }
+            }
+            if self.tries.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Ok(Response::from_bytes(
+                    StatusCode::ServiceUnavailable,
+                    Headers::new(),
+                    "",
+                ));
+            }
+            let mut headers = Headers::new();
+            headers.insert(CONTENT_LENGTH, "11");
+            Ok(Response::from_bytes(StatusCode::Ok, headers, "hello world"))
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_upload_retries_and_download() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let callback = {
+            let reported = reported.clone();
+            ProgressCallback::new(move |progress| reported.lock().unwrap().push(progress))
+        };
+        let ctx = Context::new().with_value(callback);
+
+        let retry = RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(1))
+                .max_retries(1u32),
+        )
+        .to_policy();
+        let next: Vec<Arc<dyn Policy>> = vec![retry, Arc::new(Transport::default())];
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Put);
+        let body: Box<dyn SeekableStream> = Box::new(BytesStream::new("upload"));
+        request.set_body(body);
+
+        let response = TransferProgressPolicy
+            .send(&ctx, &mut request, &next)
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::Ok, response.status());
+        response.into_raw_body().collect().await.unwrap();
+
+        let upload = |bytes_transferred| TransferProgress {
+            direction: TransferDirection::Upload,
+            bytes_transferred,
+            total_bytes: Some(6),
+        };
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![
+                upload(6),
+                upload(0),
+                upload(6),
+                TransferProgress {
+                    direction: TransferDirection::Download,
+                    bytes_transferred: 11,
+                    total_bytes: Some(11),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    async_runtime,
+    date::{self, clock_from_context, OffsetDateTime},
+    http::{
+        policies::{authority, get_retry_after_with_clock, Policy, PolicyResult},
+        Cancellation, Context, RateLimitOptions, Request, StatusCode,
+    },
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing::debug;
+
+/// The lowest rate, as a fraction of the configured rate, that the rate is reduced to after throttled responses.
+const MIN_RATE_FRACTION: f64 = 1.0 / 16.0;
+
+/// The fraction of the configured rate that the rate recovers by with each successful response.
+const RECOVERY_RATE_FRACTION: f64 = 1.0 / 16.0;
+
+/// [`Policy`] to limit the rate at which requests are sent using a token bucket.
+///
+/// Each try waits until a token is available rather than failing. The rate adapts down when the service
+/// responds with `429 Too Many Requests`. Buckets are shared by all clones of this policy.
+///
+/// Tokens are refilled using the [`Clock`](crate::date::Clock) in the [`Context`], if any.
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    options: RateLimitOptions,
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// Available tokens. Negative when tokens have been reserved by waiting requests.
+    tokens: f64,
+    /// Current rate in tokens per second.
+    rate: f64,
+    last_refill: OffsetDateTime,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, burst: f64, now: OffsetDateTime) {
+        if now > self.last_refill {
+            let elapsed = date::diff(now, self.last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.rate).min(burst);
+            self.last_refill = now;
+        }
+    }
+}
+
+impl RateLimitPolicy {
+    /// Creates a `RateLimitPolicy`.
+    ///
+    /// If `requests_per_second` is not positive, including NaN, the rate is not limited.
+    pub fn new(options: RateLimitOptions) -> Self {
+        Self {
+            options,
+            buckets: Arc::default(),
+        }
+    }
+
+    fn is_disabled(&self) -> bool {
+        let rate = self.options.requests_per_second;
+        rate.is_nan() || rate <= 0.0
+    }
+
+    fn key(&self, request: &Request) -> String {
+        if self.options.per_host {
+            authority(request.url())
+        } else {
+            String::new()
+        }
+    }
+
+    /// Reserves a token at `now` and returns how long to wait before it is available.
+    fn acquire(&self, key: &str, now: OffsetDateTime) -> Duration {
+        let burst = f64::from(self.options.burst.max(1));
+        let mut buckets = self.buckets.lock().expect("lock poisoned");
+        let bucket = buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: burst,
+            rate: self.options.requests_per_second,
+            last_refill: now,
+        });
+        bucket.refill(burst, now);
+
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(-bucket.tokens / bucket.rate).unwrap_or(Duration::MAX)
+        }
+    }
+
+    /// Halves the rate and defers further requests by `retry_after`.
+    fn throttled(&self, key: &str, retry_after: Option<Duration>) {
+        let mut buckets = self.buckets.lock().expect("lock poisoned");
+        if let Some(bucket) = buckets.get_mut(key) {
+            let min_rate = self.options.requests_per_second * MIN_RATE_FRACTION;
+            bucket.rate = (bucket.rate / 2.0).max(min_rate);
+            let retry_after = retry_after.unwrap_or_default().as_secs_f64();
+            bucket.tokens = bucket.tokens.min(0.0) - retry_after * bucket.rate;
+            debug!(
+                "throttled; reduced rate to {} requests per second",
+                bucket.rate
+            );
+        }
+    }
+
+    fn succeeded(&self, key: &str) {
+        let mut buckets = self.buckets.lock().expect("lock poisoned");
+        if let Some(bucket) = buckets.get_mut(key) {
+            let rate = self.options.requests_per_second;
+            bucket.rate = (bucket.rate + rate * RECOVERY_RATE_FRACTION).min(rate);
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RateLimitPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if self.is_disabled() {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+
+        let clock = clock_from_context(ctx);
+        let key = self.key(request);
+        let wait = self.acquire(&key, clock.now());
+        if !wait.is_zero() {
+            debug!("waiting {wait:?} to send request within rate limit");
+            let sleep = async_runtime::from_context(ctx).sleep(wait);
+            Cancellation::from_context(ctx)
+                .run(async {
+                    sleep.await;
+                    Ok(())
+                })
+                .await?;
+        }
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        match response.status() {
+            StatusCode::TooManyRequests => self.throttled(
+                &key,
+                get_retry_after_with_clock(response.headers(), clock.as_ref()),
+            ),
+            status if status.is_success() => self.succeeded(&key),
+            _ => {}
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        date::{Clock, ManualClock},
+        http::{headers::Headers, Method, Response},
+    };
+
+    fn policy() -> RateLimitPolicy {
+        RateLimitPolicy::new(
+            RateLimitOptions::default()
+                .requests_per_second(10.0)
+                .burst(2u32),
+        )
+    }
+
+    #[test]
+    fn waits_after_burst() {
+        let policy = policy();
+        let clock = ManualClock::default();
+        assert_eq!(Duration::ZERO, policy.acquire("", clock.now()));
+        assert_eq!(Duration::ZERO, policy.acquire("", clock.now()));
+
+        assert_eq!(Duration::from_millis(100), policy.acquire("", clock.now()));
+        assert_eq!(Duration::from_millis(200), policy.acquire("", clock.now()));
+
+        // Tokens are refilled as the clock advances.
+        clock.advance(Duration::from_millis(300));
+        assert_eq!(Duration::ZERO, policy.acquire("", clock.now()));
+
+        // Buckets are per key.
+        assert_eq!(Duration::ZERO, policy.clone().acquire("other", clock.now()));
+    }
+
+    #[tokio::test]
+    async fn invalid_rates_are_not_limited() {
+        for rate in [0.0, -1.0, f64::NAN] {
+            let policy = RateLimitPolicy::new(
+                RateLimitOptions::default()
+                    .requests_per_second(rate)
+                    .burst(1u32),
+            );
+            let transport: Arc<dyn Policy> = Arc::new(Responder);
+            for _ in 0..3 {
+                let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+                policy
+                    .send(&Context::new(), &mut request, &[transport.clone()])
+                    .await
+                    .unwrap();
+            }
+            assert!(policy.buckets.lock().unwrap().is_empty());
+        }
+
+        let policy = RateLimitPolicy::new(
+            RateLimitOptions::default()
+                .requests_per_second(f64::INFINITY)
+                .burst(1u32),
+        );
+        let now = OffsetDateTime::now_utc();
+        assert_eq!(Duration::ZERO, policy.acquire("", now));
+        assert_eq!(Duration::ZERO, policy.acquire("", now));
+    }
+
+    #[test]
+    fn adapts_to_throttling() {
+        let policy = policy();
+        let now = OffsetDateTime::now_utc();
+        policy.acquire("", now);
+        policy.throttled("", Some(Duration::from_secs(1)));
+        assert_eq!(5.0, policy.buckets.lock().unwrap()[""].rate);
+
+        // The retry-after period must elapse before the next token is available.
+        let wait = policy.acquire("", now);
+        assert!(wait > Duration::from_millis(1000) && wait <= Duration::from_millis(1200));
+
+        for _ in 0..16 {
+            policy.succeeded("");
+        }
+        assert_eq!(10.0, policy.buckets.lock().unwrap()[""].rate);
+    }
+
+    #[derive(Debug)]
+    struct Responder;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Responder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            Ok(Response::from_bytes(StatusCode::Ok, Headers::new(), ""))
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_rate() {
+        let policy = RateLimitPolicy::new(
+            RateLimitOptions::default()
+                .requests_per_second(50.0)
+                .burst(1u32),
+        );
+        let transport: Arc<dyn Policy> = Arc::new(Responder);
+
+        let start = OffsetDateTime::now_utc();
+        for _ in 0..3 {
+            let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+            policy
+                .send(&Context::new(), &mut request, &[transport.clone()])
+                .await
+                .unwrap();
+        }
+        assert!(date::diff(OffsetDateTime::now_utc(), start) >= Duration::from_millis(35));
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::{
+    headers::{Headers, CACHE_CONTROL, CONTENT_LENGTH, ETAG, IF_NONE_MATCH},
+    policies::{is_range_request, Policy, PolicyResult},
+    Context, Etag, Method, Request, Response, ResponseCacheOptions, StatusCode,
+};
+use bytes::Bytes;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tracing::debug;
+
+/// A response stored by the [`ResponseCachePolicy`].
+#[derive(Clone, Debug)]
+pub struct CachedResponse {
+    /// The `ETag` of the response, sent in the `If-None-Match` header to revalidate it.
+    pub etag: Etag,
+
+    /// The status of the response.
+    pub status: StatusCode,
+
+    /// The headers of the response.
+    pub headers: Headers,
+
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+impl CachedResponse {
+    /// Gets the approximate number of bytes used by the response.
+    pub fn size(&self) -> usize {
+        let headers: usize = self
+            .headers
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.as_str().len())
+            .sum();
+        self.etag.as_ref().len() + headers + self.body.len()
+    }
+}
+
+/// Storage for responses cached by the [`ResponseCachePolicy`].
+///
+/// Keys are opaque strings derived from the [`ResponseCacheOptions::partition`], the request URL,
+/// and the headers in [`ResponseCacheOptions::vary`].
+/// A store may evict responses at any time.
+pub trait ResponseCache: Send + Sync + fmt::Debug {
+    /// Gets the response stored for `key`, if any.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Stores `response` for `key`, replacing any response already stored.
+    fn insert(&self, key: String, response: CachedResponse);
+
+    /// Removes any response stored for `key`.
+    fn remove(&self, key: &str);
+}
+
+/// A [`ResponseCache`] in memory that evicts the least recently used responses to stay within a size limit.
+///
+/// Clones share the same responses.
+#[derive(Clone, Debug)]
+pub struct MemoryResponseCache {
+    max_size: usize,
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    responses: HashMap<String, (CachedResponse, u64)>,
+    /// Keys by when they were last used.
+    recency: BTreeMap<u64, String>,
+    last_used: u64,
+    size: usize,
+}
+
+impl Entries {
+    fn touch(&mut self, key: &str) -> Option<&CachedResponse> {
+        self.last_used += 1;
+        let (response, used) = self.responses.get_mut(key)?;
+        self.recency.remove(used);
+        *used = self.last_used;
+        self.recency.insert(self.last_used, key.to_string());
+        Some(response)
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((response, used)) = self.responses.remove(key) {
+            self.recency.remove(&used);
+            self.size -= response.size();
+        }
+    }
+}
+
+impl MemoryResponseCache {
+    /// Creates a `MemoryResponseCache` that stores up to `max_size` bytes of responses.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            entries: Arc::default(),
+        }
+    }
+
+    /// Gets the maximum number of bytes of responses stored.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Gets the number of bytes of responses stored.
+    pub fn size(&self) -> usize {
+        self.entries.lock().expect("lock poisoned").size
+    }
+}
+
+impl ResponseCache for MemoryResponseCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().expect("lock poisoned");
+        entries.touch(key).cloned()
+    }
+
+    fn insert(&self, key: String, response: CachedResponse) {
+        let mut entries = self.entries.lock().expect("lock poisoned");
+        entries.remove(&key);
+        let size = response.size();
+        if size > self.max_size {
+            return;
+        }
+        while entries.size + size > self.max_size {
+            let Some((_, evicted)) = entries.recency.pop_first() else {
+                break;
+            };
+            if let Some((response, _)) = entries.responses.remove(&evicted) {
+                entries.size -= response.size();
+            }
+        }
+        entries.last_used += 1;
+        let used = entries.last_used;
+        entries.recency.insert(used, key.clone());
+        entries.responses.insert(key, (response, used));
+        entries.size += size;
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().expect("lock poisoned").remove(key);
+    }
+}
+
+/// [`Policy`] to cache `GET` responses and revalidate them with their `ETag`.
+///
+/// Each try is sent with an `If-None-Match` header if a response for the request is cached,
+/// and a `304 Not Modified` response is replaced with the cached response updated with the headers of the `304` response.
+/// See [`ResponseCacheOptions`] for which responses are cached.
+#[derive(Debug, Clone)]
+pub struct ResponseCachePolicy {
+    options: ResponseCacheOptions,
+}
+
+impl ResponseCachePolicy {
+    pub fn new(options: ResponseCacheOptions) -> Self {
+        Self { options }
+    }
+
+    fn key(&self, request: &Request) -> String {
+        // The partition is prefixed with its length so that it cannot run into the URL.
+        let mut key = match &self.options.partition {
+            Some(partition) => format!("{}:{partition}\n", partition.len()),
+            None => String::from("\n"),
+        };
+        key.push_str(request.url().as_str());
+        for name in &self.options.vary {
+            if let Some(value) = request.headers().get_optional_str(name) {
+                key.push('\n');
+                key.push_str(name.as_str());
+                key.push(':');
+                key.push_str(value);
+            }
+        }
+        key
+    }
+
+    /// Gets the `ETag` of a response that may be cached.
+    fn cacheable(&self, headers: &Headers) -> Option<Etag> {
+        let no_store = headers
+            .get_optional_str(&CACHE_CONTROL)
+            .is_some_and(|value| {
+                value
+                    .split(',')
+                    .any(|directive| directive.trim().eq_ignore_ascii_case("no-store"))
+            });
+        let length: usize = headers.get_optional_str(&CONTENT_LENGTH)?.parse().ok()?;
+        if no_store || length > self.options.max_body_size {
+            return None;
+        }
+        headers.get_optional_str(&ETAG).map(Etag::from)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for ResponseCachePolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if request.method() != &Method::Get
+            || request.headers().get_optional_str(&IF_NONE_MATCH).is_some()
+            || is_range_request(request)
+        {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+
+        let key = self.key(request);
+        let cached = self.options.store.get(&key);
+        if let Some(cached) = &cached {
+            request.insert_header(IF_NONE_MATCH, cached.etag.to_string());
+        }
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        if cached.is_some() {
+            // Remove the header so the next try is not mistaken for a conditional request by the caller.
+            request.headers.remove(&IF_NONE_MATCH);
+        }
+        let response = result?;
+
+        match (response.status(), cached) {
+            (StatusCode::NotModified, Some(cached)) => {
+                debug!("using cached response for {}", request.url());
+                let mut headers = cached.headers;
+                for (name, value) in response.headers().iter() {
+                    // The length describes the empty body of the `304` response.
+                    if name != &CONTENT_LENGTH {
+                        headers.insert(name.clone(), value.clone());
+                    }
+                }
+                return Ok(Response::from_bytes(cached.status, headers, cached.body));
+            }
+            (StatusCode::Ok, _) => {}
+            (status, _) => {
+                if status.is_success() {
+                    self.options.store.remove(&key);
+                }
+                return Ok(response);
+            }
+        }
+
+        let Some(etag) = self.cacheable(response.headers()) else {
+            self.options.store.remove(&key);
+            return Ok(response);
+        };
+        let (status, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+        self.options.store.insert(
+            key,
+            CachedResponse {
+                etag,
+                status,
+                headers: headers.clone(),
+                body: body.clone(),
+            },
+        );
+        Ok(Response::from_bytes(status, headers, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::headers::{HeaderName, MS_RANGE, RANGE, REQUEST_ID};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns a response with an `ETag`, or `304 Not Modified` if the request has a matching `If-None-Match`.
+    #[derive(Debug, Default)]
+    struct Transport {
+        requests: AtomicUsize,
+        conditional_requests: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let etag = format!("\"{}\"", request.url().path());
+            let requests = self.requests.load(Ordering::SeqCst).to_string();
+            if request.headers().get_optional_str(&IF_NONE_MATCH).is_some() {
+                self.conditional_requests.fetch_add(1, Ordering::SeqCst);
+            }
+            if request.headers().get_optional_str(&IF_NONE_MATCH) == Some(etag.as_str()) {
+                let mut headers = Headers::new();
+                headers.insert(REQUEST_ID, requests);
+                headers.insert(CONTENT_LENGTH, "0");
+                return Ok(Response::from_bytes(StatusCode::NotModified, headers, ""));
+            }
+            let body = request.url().path().to_string();
+            let mut headers = Headers::new();
+            headers.insert(REQUEST_ID, requests);
+            headers.insert(ETAG, etag);
+            headers.insert(CONTENT_LENGTH, body.len().to_string());
+            Ok(Response::from_bytes(StatusCode::Ok, headers, body))
+        }
+    }
+
+    async fn get(policy: &ResponseCachePolicy, next: &[Arc<dyn Policy>], path: &str) -> Bytes {
+        get_with(policy, next, path, None).await.1
+    }
+
+    /// Gets `path` with an optional request header, returning the response's `x-ms-request-id` and body.
+    async fn get_with(
+        policy: &ResponseCachePolicy,
+        next: &[Arc<dyn Policy>],
+        path: &str,
+        header: Option<(HeaderName, &'static str)>,
+    ) -> (String, Bytes) {
+        let url = format!("http://localhost{path}").parse().unwrap();
+        let mut request = Request::new(url, Method::Get);
+        if let Some((name, value)) = header {
+            request.insert_header(name, value);
+        }
+        let response = policy
+            .send(&Context::new(), &mut request, next)
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::Ok, response.status());
+        assert!(request.headers().get_optional_str(&IF_NONE_MATCH).is_none());
+        let request_id = response.headers().get_str(&REQUEST_ID).unwrap().to_string();
+        (
+            request_id,
+            response.into_raw_body().collect().await.unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn serves_cached_response_when_not_modified() {
+        let transport = Arc::new(Transport::default());
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let policy = ResponseCachePolicy::new(ResponseCacheOptions::default());
+
+        assert_eq!("/secrets/a", get(&policy, &next, "/secrets/a").await);
+        let (request_id, body) = get_with(&policy, &next, "/secrets/a", None).await;
+        assert_eq!("/secrets/a", body);
+        assert_eq!(2, transport.requests.load(Ordering::SeqCst));
+        // The headers of the `304` response replace the cached ones.
+        assert_eq!("2", request_id);
+
+        // Requests with their own `If-None-Match` get the service's response.
+        let mut request = Request::new("http://localhost/secrets/a".parse().unwrap(), Method::Get);
+        request.insert_header(IF_NONE_MATCH, "\"/secrets/a\"");
+        let response = policy.send(&Context::new(), &mut request, &next).await;
+        assert_eq!(StatusCode::NotModified, response.unwrap().status());
+    }
+
+    #[tokio::test]
+    async fn skips_ranged_requests() {
+        let transport = Arc::new(Transport::default());
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let policy = ResponseCachePolicy::new(ResponseCacheOptions::default());
+        get(&policy, &next, "/blobs/a").await;
+
+        // The cached full body must not be returned for a `304 Not Modified` response to a ranged request.
+        for header in [(RANGE, "bytes=0-1"), (MS_RANGE, "bytes=0-1")] {
+            get_with(&policy, &next, "/blobs/a", Some(header)).await;
+        }
+        assert_eq!(3, transport.requests.load(Ordering::SeqCst));
+        assert_eq!(0, transport.conditional_requests.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn separates_partitions() {
+        let transport = Arc::new(Transport::default());
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let store: Arc<dyn ResponseCache> = Arc::new(MemoryResponseCache::new(1024));
+        let policy = |partition: &str| {
+            ResponseCachePolicy::new(
+                ResponseCacheOptions::default()
+                    .store(store.clone())
+                    .partition(partition.to_string()),
+            )
+        };
+
+        get(&policy("a"), &next, "/secrets/a").await;
+        get(&policy("b"), &next, "/secrets/a").await;
+        assert_eq!(0, transport.conditional_requests.load(Ordering::SeqCst));
+        get(&policy("a"), &next, "/secrets/a").await;
+        assert_eq!(1, transport.conditional_requests.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let response = |body: &'static str| CachedResponse {
+            etag: Etag::from("1"),
+            status: StatusCode::Ok,
+            headers: Headers::new(),
+            body: Bytes::from(body),
+        };
+        let cache = MemoryResponseCache::new(12);
+        cache.insert("a".to_string(), response("aaaaa"));
+        cache.insert("b".to_string(), response("bbbbb"));
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c".to_string(), response("ccccc"));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+        assert_eq!(12, cache.size());
+
+        // Responses larger than the cache are not stored.
+        cache.insert("d".to_string(), response("ddddddddddddd"));
+        assert!(cache.get("d").is_none());
+        assert_eq!(12, cache.size());
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::{
+    policies::{Policy, PolicyResult},
+    Context, Request, RetryOptions,
+};
+use std::sync::Arc;
+use tracing::trace;
+
+/// Retry policy configured from the client's [`RetryOptions`] that can be overridden per call.
+///
+/// If the [`Context`] for a call contains [`RetryOptions`], a retry policy is created from those options
+/// and used only for that call; otherwise, the retry policy configured for the client is used.
+#[derive(Debug, Clone)]
+pub(crate) struct ContextRetryPolicy {
+    policy: Arc<dyn Policy>,
+}
+
+impl ContextRetryPolicy {
+    pub(crate) fn new(options: &RetryOptions) -> Self {
+        Self {
+            policy: options.to_policy(),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for ContextRetryPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if let Some(options) = ctx.value::<RetryOptions>() {
+            trace!("overriding retry policy from context with {options:?}");
+            return options.to_policy().send(ctx, request, next).await;
+        }
+
+        self.policy.send(ctx, request, next).await
+    }
+}
@@ -1,12 +1,14 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use super::RetryPolicy;
+use super::{jitter, RetryPolicy};
+use crate::http::{Jitter, RetryBudget};
 use std::time::Duration;
 
 /// Retry policy with exponential back-off.
 ///
-/// Retry policy with exponential back-off (with an added random delay up to 256 ms). Each retry
+/// Retry policy with exponential back-off (randomized according to the configured [`Jitter`],
+/// by default with an added random delay up to 256 ms). Each retry
 /// will happen at least after an exponential wait time. So if x is the first retry wait, the
 /// second will be x*2, the third x*4 and so on. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
@@ -17,6 +19,8 @@ pub struct ExponentialRetryPolicy {
     max_retries: u32,
     max_elapsed: Duration,
     max_delay: Duration,
+    jitter: Jitter,
+    budget: Option<RetryBudget>,
 }
 
 impl ExponentialRetryPolicy {
@@ -25,12 +29,16 @@ impl ExponentialRetryPolicy {
         max_retries: u32,
         max_elapsed: Duration,
         max_delay: Duration,
+        jitter: Jitter,
+        budget: Option<RetryBudget>,
     ) -> Self {
         Self {
             initial_delay: initial_delay.max(Duration::from_millis(1)),
             max_retries,
             max_elapsed,
             max_delay: max_delay.max(Duration::from_secs(1)),
+            jitter,
+            budget,
         }
     }
 }
@@ -41,10 +49,22 @@ impl RetryPolicy for ExponentialRetryPolicy {
     }
 
     fn sleep_duration(&self, retry_count: u32) -> Duration {
-        let sleep_ms = self.initial_delay.as_millis() as u64 * 2u64.pow(retry_count)
-            + u64::from(rand::random::<u8>());
-        let sleep_ms = sleep_ms.min(self.max_delay.as_millis().try_into().unwrap_or(u64::MAX));
-        Duration::from_millis(sleep_ms)
+        let delay = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(retry_count))
+            .min(self.max_delay);
+        jitter::apply(
+            self.jitter,
+            delay,
+            self.initial_delay,
+            self.max_delay,
+            retry_count,
+        )
+        .min(self.max_delay)
+    }
+
+    fn budget(&self) -> Option<&RetryBudget> {
+        self.budget.as_ref()
     }
 }
 
@@ -61,6 +81,8 @@ mod tests {
             options.max_retries,
             options.max_total_elapsed,
             options.max_delay,
+            options.jitter,
+            options.budget,
         );
 
         let mut elapsed_time = Duration::from_secs(0);
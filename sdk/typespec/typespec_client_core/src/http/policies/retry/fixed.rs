@@ -1,11 +1,14 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+use super::jitter;
+use crate::http::{Jitter, RetryBudget};
 use std::time::Duration;
 
 /// Retry policy with a fixed back-off.
 ///
-/// Retry policy with fixed back-off (with an added random delay up to 256 ms). Each retry will
+/// Retry policy with fixed back-off (randomized according to the configured [`Jitter`],
+/// by default with an added random delay up to 256 ms). Each retry will
 /// happen at least after the same, configured sleep time. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
 /// wait time is not precise.
@@ -14,14 +17,24 @@ pub struct FixedRetryPolicy {
     delay: Duration,
     max_retries: u32,
     max_elapsed: Duration,
+    jitter: Jitter,
+    budget: Option<RetryBudget>,
 }
 
 impl FixedRetryPolicy {
-    pub(crate) fn new(delay: Duration, max_retries: u32, max_elapsed: Duration) -> Self {
+    pub(crate) fn new(
+        delay: Duration,
+        max_retries: u32,
+        max_elapsed: Duration,
+        jitter: Jitter,
+        budget: Option<RetryBudget>,
+    ) -> Self {
         Self {
             delay: delay.max(Duration::from_millis(10)),
             max_retries,
             max_elapsed,
+            jitter,
+            budget,
         }
     }
 }
@@ -31,8 +44,17 @@ impl super::RetryPolicy for FixedRetryPolicy {
         retry_count >= self.max_retries || time_since_start >= self.max_elapsed
     }
 
-    fn sleep_duration(&self, _retry_count: u32) -> Duration {
-        let sleep_ms = self.delay.as_millis() as u64 + u64::from(rand::random::<u8>());
-        Duration::from_millis(sleep_ms)
+    fn sleep_duration(&self, retry_count: u32) -> Duration {
+        jitter::apply(
+            self.jitter,
+            self.delay,
+            self.delay,
+            self.delay.saturating_mul(3),
+            retry_count,
+        )
+    }
+
+    fn budget(&self) -> Option<&RetryBudget> {
+        self.budget.as_ref()
     }
 }
@@ -0,0 +1,64 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::Jitter;
+use rand::Rng;
+use std::time::Duration;
+
+/// Randomizes the computed `delay` before the given retry.
+///
+/// `initial_delay` and `max_delay` bound the delay for [`Jitter::Decorrelated`].
+pub(crate) fn apply(
+    jitter: Jitter,
+    delay: Duration,
+    initial_delay: Duration,
+    max_delay: Duration,
+    retry_count: u32,
+) -> Duration {
+    let mut rng = rand::thread_rng();
+    match jitter {
+        Jitter::Additive => delay + Duration::from_millis(u64::from(rng.gen::<u8>())),
+        Jitter::Full => rng.gen_range(Duration::ZERO..=delay),
+        Jitter::Equal => delay / 2 + rng.gen_range(Duration::ZERO..=delay / 2),
+        Jitter::Decorrelated => {
+            // Decorrelated jitter depends on the previous delay. Rather than tracking state for each request,
+            // sample the whole sequence up to this retry, which gives the same distribution of delays.
+            let max_delay = max_delay.max(initial_delay);
+            (0..retry_count.max(1)).fold(initial_delay, |previous, _| {
+                let upper = previous.saturating_mul(3).clamp(initial_delay, max_delay);
+                rng.gen_range(initial_delay..=upper)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_bounds() {
+        let delay = Duration::from_secs(4);
+        let initial_delay = Duration::from_secs(1);
+        let max_delay = Duration::from_secs(10);
+        for retry_count in 1..10 {
+            let full = apply(Jitter::Full, delay, initial_delay, max_delay, retry_count);
+            assert!(full <= delay);
+
+            let equal = apply(Jitter::Equal, delay, initial_delay, max_delay, retry_count);
+            assert!(equal >= delay / 2 && equal <= delay);
+
+            let decorrelated = apply(
+                Jitter::Decorrelated,
+                delay,
+                initial_delay,
+                max_delay,
+                retry_count,
+            );
+            assert!(decorrelated >= initial_delay && decorrelated <= max_delay);
+        }
+
+        let additive = apply(Jitter::Additive, delay, initial_delay, max_delay, 1);
+        assert!(additive >= delay && additive < delay + Duration::from_millis(256));
+    }
+}
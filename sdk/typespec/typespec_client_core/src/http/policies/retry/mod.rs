@@ -1,28 +1,31 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+mod context;
 mod exponential;
 mod fixed;
+mod jitter;
 mod none;
 
+pub(crate) use context::*;
 pub use exponential::*;
 pub use fixed::*;
 pub use none::*;
 
 use crate::{
-    date::{self, OffsetDateTime},
+    async_runtime::{self, default_runtime, AsyncRuntime},
+    date::{self, clock_from_context, Clock, OffsetDateTime},
     error::HttpError,
     http::{
         headers::{Headers, RETRY_AFTER, RETRY_AFTER_MS, X_MS_RETRY_AFTER_MS},
         policies::{Policy, PolicyResult},
-        Context, Request, StatusCode,
+        Cancellation, Context, Request, RetryBudget, StatusCode,
     },
-    sleep::sleep,
 };
 use async_trait::async_trait;
 use std::{sync::Arc, time::Duration};
 use tracing::{debug, trace};
-use typespec::error::{Error, ErrorKind, ResultExt};
+use typespec::error::{Error, ErrorKind, ResultExt, RETRY_STATUSES};
 
 /// Attempts to parse the supplied string as an HTTP date, of the form defined by RFC 7231 (e.g. `Fri, 01 Jan 2021 00:00:00 GMT`).
 /// Returns `None` if the string is not a valid HTTP date.
@@ -33,6 +36,16 @@ fn try_parse_retry_after_http_date(http_date: &str) -> Option<OffsetDateTime> {
 /// A function that returns an `OffsetDateTime`.
 type DateTimeFn = fn() -> OffsetDateTime;
 
+/// A [`Clock`] that gets the current time from a [`DateTimeFn`].
+#[derive(Debug)]
+struct FnClock(DateTimeFn);
+
+impl Clock for FnClock {
+    fn now(&self) -> OffsetDateTime {
+        (self.0)()
+    }
+}
+
 /// Get the duration to delay between retry attempts, provided by the headers from the response.
 ///
 /// This function checks for retry-after headers in the following order:
@@ -43,6 +56,13 @@ type DateTimeFn = fn() -> OffsetDateTime;
 ///
 /// If no header is provided, `None` is returned.
 pub fn get_retry_after(headers: &Headers, now: DateTimeFn) -> Option<Duration> {
+    get_retry_after_with_clock(headers, &FnClock(now))
+}
+
+/// Get the duration to delay between retry attempts, provided by the headers from the response.
+///
+/// This is the same as [`get_retry_after`] except that a `retry-after` date is compared to the current time from the `clock`.
+pub fn get_retry_after_with_clock(headers: &Headers, clock: &dyn Clock) -> Option<Duration> {
     // TODO: Only check Microsoft headers when constructed from azure_core (https://github.com/Azure/azure-sdk-for-rust/issues/1753)
     [RETRY_AFTER_MS, X_MS_RETRY_AFTER_MS, RETRY_AFTER]
         .iter()
@@ -52,7 +72,7 @@ pub fn get_retry_after(headers: &Headers, now: DateTimeFn) -> Option<Duration> {
                     // RETRY_AFTER values are either in seconds or a HTTP date
                     v.parse::<u64>().ok().map(Duration::from_secs).or_else(|| {
                         try_parse_retry_after_http_date(v).map(|retry_after_datetime| {
-                            let now = now();
+                            let now = clock.now();
                             if retry_after_datetime < now {
                                 Duration::from_secs(0)
                             } else {
@@ -68,6 +88,12 @@ pub fn get_retry_after(headers: &Headers, now: DateTimeFn) -> Option<Duration> {
         })
 }
 
+/// The number of the current try of a request, starting at 1.
+///
+/// The retry policy adds this to the [`Context`] passed to the policies after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TryNumber(pub u32);
+
 /// A retry policy.
 ///
 /// In the simple form, the policies need only differ in how
@@ -84,31 +110,41 @@ pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
     fn is_expired(&self, duration_since_start: Duration, retry_count: u32) -> bool;
     /// Determine how long before the next retry should be attempted.
     fn sleep_duration(&self, retry_count: u32) -> Duration;
+    /// The [`RetryBudget`] shared with other requests, if any.
+    ///
+    /// Each request deposits into the budget and each retry must withdraw from it.
+    fn budget(&self) -> Option<&RetryBudget> {
+        None
+    }
     /// A Future that will wait until the request can be retried.
     /// `error` is the [`Error`] value the led to a retry attempt.
     /// `retry_after` is the duration to wait before retrying, if provided by the server response.
-    async fn wait(&self, _error: &Error, retry_count: u32, retry_after: Option<Duration>) {
+    ///
+    /// This waits on the [`default_runtime`] using [`RetryPolicy::wait_with_runtime`].
+    async fn wait(&self, error: &Error, retry_count: u32, retry_after: Option<Duration>) {
+        self.wait_with_runtime(default_runtime().as_ref(), error, retry_count, retry_after)
+            .await;
+    }
+    /// A Future that will wait on the `runtime` until the request can be retried.
+    ///
+    /// The retry policy calls this method with the runtime of the client, so implementations that change how to wait
+    /// should override this method rather than [`RetryPolicy::wait`].
+    async fn wait_with_runtime(
+        &self,
+        runtime: &dyn AsyncRuntime,
+        _error: &Error,
+        retry_count: u32,
+        retry_after: Option<Duration>,
+    ) {
         let policy_sleep_duration = self.sleep_duration(retry_count);
         // If the server provided a retry-after header, use the max of that and the policy sleep duration
         let sleep_duration = retry_after.map_or(policy_sleep_duration, |retry_after| {
             std::cmp::max(retry_after, policy_sleep_duration)
         });
-        sleep(sleep_duration).await;
+        runtime.sleep(sleep_duration).await;
     }
 }
 
-/// The status codes where a retry should be attempted.
-///
-/// On all other 4xx and 5xx status codes no retry is attempted.
-const RETRY_STATUSES: &[StatusCode] = &[
-    StatusCode::RequestTimeout,
-    StatusCode::TooManyRequests,
-    StatusCode::InternalServerError,
-    StatusCode::BadGateway,
-    StatusCode::ServiceUnavailable,
-    StatusCode::GatewayTimeout,
-];
-
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl<T> Policy for T
@@ -123,6 +159,12 @@ where
     ) -> PolicyResult {
         let mut retry_count = 0;
         let mut start = None;
+        let cancellation = Cancellation::from_context(ctx);
+        let runtime = async_runtime::from_context(ctx);
+        let clock = clock_from_context(ctx);
+        if let Some(budget) = self.budget() {
+            budget.deposit();
+        }
 
         loop {
             if retry_count > 0 {
@@ -131,16 +173,13 @@ where
                     "failed to reset body stream before retrying request",
                 )?;
             }
-            let result = next[0].send(ctx, request, &next[1..]).await;
+            let try_ctx = Context::with_context(ctx).with_value(TryNumber(retry_count + 1));
+            let result = next[0].send(&try_ctx, request, &next[1..]).await;
             // only start keeping track of time after the first request is made
-            let start = start.get_or_insert_with(OffsetDateTime::now_utc);
+            let start = *start.get_or_insert_with(|| clock.now());
             let (last_error, retry_after) = match result {
                 Ok(response) if response.status().is_success() => {
-                    trace!(
-                        "Successful response. Request={:?} response={:?}",
-                        request,
-                        response
-                    );
+                    trace!("successful response with status {}", response.status());
                     return Ok(response);
                 }
                 Ok(response) => {
@@ -152,7 +191,7 @@ where
                     // https://learn.microsoft.com/en-us/azure/architecture/best-practices/retry-service-specific#retry-usage-guidance
                     let retry_after = match status {
                         StatusCode::TooManyRequests | StatusCode::ServiceUnavailable => {
-                            get_retry_after(response.headers(), OffsetDateTime::now_utc)
+                            get_retry_after_with_clock(response.headers(), clock.as_ref())
                         }
                         _ => None,
                     };
@@ -202,16 +241,30 @@ where
                 }
             };
 
-            let time_since_start = (OffsetDateTime::now_utc() - *start)
-                .try_into()
-                .unwrap_or_default();
+            let time_since_start = (clock.now() - start).try_into().unwrap_or_default();
             if self.is_expired(time_since_start, retry_count) {
                 return Err(last_error
                     .context("retry policy expired and the request will no longer be retried"));
             }
+            if self.budget().is_some_and(|budget| !budget.try_withdraw()) {
+                return Err(last_error
+                    .context("retry budget exhausted and the request will no longer be retried"));
+            }
             retry_count += 1;
 
-            self.wait(&last_error, retry_count, retry_after).await;
+            // Stop waiting if the operation is cancelled or its deadline passes.
+            let wait = async {
+                self.wait_with_runtime(runtime.as_ref(), &last_error, retry_count, retry_after)
+                    .await;
+                Ok(())
+            };
+            if let Err(error) = cancellation.run(wait).await {
+                return Err(Error::full(
+                    error.kind().clone(),
+                    last_error,
+                    format!("{error} and the request will no longer be retried"),
+                ));
+            }
         }
     }
 }
@@ -220,11 +273,12 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::date::ManualClock;
     use time::macros::datetime;
 
-    // A function that returns a fixed "now" value for testing.
-    fn datetime_now() -> OffsetDateTime {
-        datetime!(2021-01-01 0:00:00 UTC)
+    // A clock that returns a fixed "now" value for testing.
+    fn clock() -> ManualClock {
+        ManualClock::new(datetime!(2021-01-01 0:00:00 UTC))
     }
 
     #[test]
@@ -251,30 +305,36 @@ mod test {
         // Test parsing a valid HTTP date that is 10 secs in the future
         let mut headers = Headers::new();
         headers.insert(RETRY_AFTER, "Fri, 01 Jan 2021 00:00:10 GMT");
-        let retry_after = get_retry_after(&headers, datetime_now);
+        let retry_after = get_retry_after_with_clock(&headers, &clock());
         assert_eq!(retry_after, Some(Duration::from_secs(10)));
 
         // Test parsing a valid HTTP date that is in the past returns 0
         let mut headers = Headers::new();
         headers.insert(RETRY_AFTER, "Thu, 31 Dec 2020 23:59:50 GMT");
-        let retry_after = get_retry_after(&headers, datetime_now);
+        let retry_after = get_retry_after_with_clock(&headers, &clock());
         assert_eq!(retry_after, Some(Duration::from_secs(0)));
 
         // Test that when no retry headers are present, None is returned
         let headers = Headers::new();
-        let retry_after = get_retry_after(&headers, datetime_now);
+        let retry_after = get_retry_after_with_clock(&headers, &clock());
         assert_eq!(retry_after, None);
 
         // Test parsing an invalid HTTP date
         let mut headers = Headers::new();
         headers.insert(RETRY_AFTER, "invalid");
-        let retry_after = get_retry_after(&headers, datetime_now);
+        let retry_after = get_retry_after_with_clock(&headers, &clock());
         assert_eq!(retry_after, None);
 
         // Test `RETRY_AFTER` parsing an integer value
         let mut headers = Headers::new();
         headers.insert(RETRY_AFTER, "123");
-        let retry_after = get_retry_after(&headers, datetime_now);
+        let retry_after = get_retry_after_with_clock(&headers, &clock());
         assert_eq!(retry_after, Some(Duration::from_secs(123)));
+
+        // Test the current time from a function
+        let mut headers = Headers::new();
+        headers.insert(RETRY_AFTER, "Fri, 01 Jan 2021 00:00:10 GMT");
+        let retry_after = get_retry_after(&headers, || datetime!(2021-01-01 0:00:00 UTC));
+        assert_eq!(retry_after, Some(Duration::from_secs(10)));
     }
 }
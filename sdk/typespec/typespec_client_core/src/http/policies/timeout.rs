@@ -0,0 +1,117 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    async_runtime,
+    http::{
+        policies::{Policy, PolicyResult},
+        Context, Request,
+    },
+    sleep::Timeout,
+};
+use std::{sync::Arc, time::Duration};
+use typespec::error::{Error, ErrorKind};
+
+/// [`Policy`] to abandon a single try that does not complete within a timeout.
+///
+/// A try that times out returns an [`ErrorKind::Io`] error, so it is retried by the retry policy.
+#[derive(Debug, Clone)]
+pub struct TimeoutPolicy {
+    timeout: Duration,
+}
+
+impl TimeoutPolicy {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for TimeoutPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let deadline = async_runtime::from_context(ctx).sleep(self.timeout);
+        Timeout::new(next[0].send(ctx, request, &next[1..]), deadline)
+            .await
+            .map_err(|error| {
+                Error::full(
+                    ErrorKind::Io,
+                    error,
+                    format!("request did not complete within {:?}", self.timeout),
+                )
+            })?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        headers::Headers, ClientOptions, FixedRetryOptions, Method, Pipeline, Response,
+        RetryOptions, StatusCode, TransportOptions,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct HangsOnce {
+        tries: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for HangsOnce {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            if self.tries.fetch_add(1, Ordering::SeqCst) == 0 {
+                futures::future::pending::<()>().await;
+            }
+            Ok(Response::from_bytes(StatusCode::Ok, Headers::new(), ""))
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_timed_out_try() {
+        let transport = Arc::new(HangsOnce::default());
+        let options = ClientOptions {
+            retry: Some(RetryOptions::fixed(
+                FixedRetryOptions::default().delay(Duration::from_millis(10)),
+            )),
+            per_try_timeout: Some(Duration::from_millis(50)),
+            transport: Some(TransportOptions::new_custom_policy(transport.clone())),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let response = pipeline
+            .send::<()>(&Context::new(), &mut request)
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::Ok, response.status());
+        assert_eq!(2, transport.tries.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn timed_out_try_is_io_error() {
+        let transport: Arc<dyn Policy> = Arc::new(HangsOnce::default());
+        let policy = TimeoutPolicy::new(Duration::from_millis(10));
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        let err = policy
+            .send(&Context::new(), &mut request, &[transport])
+            .await
+            .unwrap_err();
+
+        assert_eq!(&ErrorKind::Io, err.kind());
+    }
+}
@@ -4,11 +4,10 @@
 use crate::http::{
     options::TransportOptions,
     policies::{Policy, PolicyResult},
-    Context, Request,
+    Cancellation, Context, Request,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
-use tracing::debug;
 
 #[derive(Debug, Clone)]
 pub struct TransportPolicy {
@@ -33,9 +32,9 @@ impl Policy for TransportPolicy {
         // there must be no more policies
         assert_eq!(0, next.len());
 
-        debug!("the following request will be passed to the transport policy: {request:#?}");
+        // Abort the request if the operation is cancelled or its deadline passes.
         let response = { self.transport_options.send(ctx, request) };
 
-        response.await
+        Cancellation::from_context(ctx).run(response).await
     }
 }
@@ -1,12 +1,19 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::http::{headers::Headers, StatusCode};
+use crate::{
+    async_runtime::AsyncRuntime,
+    http::{
+        headers::{Headers, CLIENT_REQUEST_ID, REQUEST_ID},
+        StatusCode,
+    },
+    stream::{BandwidthLimiter, ProgressCallback, Throttled, TransferDirection, TransferProgress},
+};
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use serde::de::DeserializeOwned;
 use std::future::Future;
-use std::{fmt, marker::PhantomData, pin::Pin};
+use std::{fmt, marker::PhantomData, pin::Pin, sync::Arc};
 use typespec::error::{ErrorKind, ResultExt};
 
 #[cfg(feature = "derive")]
@@ -100,6 +107,16 @@ impl<T> Response<T> {
         &self.headers
     }
 
+    /// Get the ID the service assigned to the request from the `x-ms-request-id` header, if any.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get_optional_str(&REQUEST_ID)
+    }
+
+    /// Get the ID the client assigned to the request from the `x-ms-client-request-id` header, if returned by the service.
+    pub fn client_request_id(&self) -> Option<&str> {
+        self.headers.get_optional_str(&CLIENT_REQUEST_ID)
+    }
+
     /// Deconstruct the HTTP response into its components.
     pub fn deconstruct(self) -> (StatusCode, Headers, ResponseBody) {
         (self.status, self.headers, self.body)
@@ -285,6 +302,28 @@ impl ResponseBody {
         Self::new(Box::pin(futures::stream::once(async move { Ok(bytes) })))
     }
 
+    /// Reports the bytes received from this body to `callback` as it is consumed.
+    ///
+    /// Pass the `content-length` of the response as `total_bytes`, if known.
+    pub fn with_progress(self, callback: ProgressCallback, total_bytes: Option<u64>) -> Self {
+        let mut bytes_received = 0;
+        Self::new(Box::pin(self.0.inspect(move |chunk| {
+            if let Ok(bytes) = chunk {
+                bytes_received += bytes.len() as u64;
+                callback.report(TransferProgress {
+                    direction: TransferDirection::Download,
+                    bytes_transferred: bytes_received,
+                    total_bytes,
+                });
+            }
+        })))
+    }
+
+    /// Limits the bytes received from this body using `limiter`, waiting on `runtime`.
+    pub fn throttled(self, limiter: BandwidthLimiter, runtime: Arc<dyn AsyncRuntime>) -> Self {
+        Self::new(Box::pin(Throttled::new(self.0, limiter, runtime)))
+    }
+
     /// Collect the stream into a [`Bytes`] collection.
     pub async fn collect(mut self) -> crate::Result<Bytes> {
         let mut final_result = Vec::new();
@@ -0,0 +1,160 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Abstractions for distributed tracing.
+//!
+//! Implement [`Tracer`] and [`Span`] to export spans created by the
+//! [`DistributedTracingPolicy`](crate::http::policies::DistributedTracingPolicy)
+//! to a tracing system like OpenTelemetry.
+
+use std::{any::Any, fmt::Debug, fmt::Write, sync::Arc};
+
+/// The kind of a [`Span`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpanKind {
+    /// An internal operation, e.g. a client method call that may send several requests.
+    Internal,
+
+    /// A single request sent to a service.
+    Client,
+}
+
+/// The status of a [`Span`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpanStatus {
+    /// The operation completed without errors.
+    Ok,
+
+    /// The operation failed.
+    Error {
+        /// A description of the error.
+        description: String,
+    },
+}
+
+/// A value of an attribute recorded on a [`Span`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeValue {
+    String(String),
+    I64(i64),
+    Bool(bool),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::I64(value)
+    }
+}
+
+impl From<u16> for AttributeValue {
+    fn from(value: u16) -> Self {
+        Self::I64(value.into())
+    }
+}
+
+impl From<u32> for AttributeValue {
+    fn from(value: u32) -> Self {
+        Self::I64(value.into())
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// The [W3C trace context](https://www.w3.org/TR/trace-context/) of a [`Span`] propagated to services.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceContext {
+    /// The ID of the whole trace.
+    pub trace_id: [u8; 16],
+
+    /// The ID of the span.
+    pub span_id: [u8; 8],
+
+    /// Whether the trace is sampled.
+    pub sampled: bool,
+
+    /// Vendor-specific trace state sent in the `tracestate` header, if any.
+    pub trace_state: Option<String>,
+}
+
+impl TraceContext {
+    /// Formats the `traceparent` header value.
+    pub fn traceparent(&self) -> String {
+        fn hex(bytes: &[u8]) -> String {
+            bytes.iter().fold(String::new(), |mut s, b| {
+                let _ = write!(s, "{b:02x}");
+                s
+            })
+        }
+
+        format!(
+            "00-{}-{}-{:02x}",
+            hex(&self.trace_id),
+            hex(&self.span_id),
+            u8::from(self.sampled)
+        )
+    }
+}
+
+/// Creates [`Span`]s.
+pub trait Tracer: Send + Sync + Debug {
+    /// Starts a new span.
+    ///
+    /// If `parent` is `None`, implementations should use their ambient context, if any, as the parent.
+    fn start_span(&self, name: &str, kind: SpanKind, parent: Option<&dyn Span>) -> Arc<dyn Span>;
+}
+
+/// A unit of work within a trace.
+pub trait Span: Send + Sync + Debug {
+    /// Records an attribute on the span.
+    fn set_attribute(&self, key: &'static str, value: AttributeValue);
+
+    /// Sets the status of the span.
+    fn set_status(&self, status: SpanStatus);
+
+    /// Gets the trace context to propagate to services, if the span is recording.
+    fn trace_context(&self) -> Option<TraceContext>;
+
+    /// Ends the span.
+    fn end(&self);
+
+    /// Gets the span as [`Any`] so that a [`Tracer`] can downcast parent spans it created.
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_traceparent() {
+        let context = TraceContext {
+            trace_id: [
+                0x4b, 0xf9, 0x2f, 0x35, 0x77, 0xb3, 0x4d, 0xa6, 0xa3, 0xce, 0x92, 0x9d, 0x0e, 0x0e,
+                0x47, 0x36,
+            ],
+            span_id: [0x00, 0xf0, 0x67, 0xaa, 0x0b, 0xa9, 0x02, 0xb7],
+            sampled: true,
+            trace_state: None,
+        };
+        assert_eq!(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            context.traceparent()
+        );
+    }
+}
@@ -5,6 +5,7 @@
 
 #[macro_use]
 mod macros;
+pub mod async_runtime;
 pub mod base64;
 pub mod date;
 pub mod error;
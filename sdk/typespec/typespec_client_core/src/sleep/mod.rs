@@ -3,8 +3,10 @@
 
 //! Sleep functions.
 
-#[cfg(any(not(feature = "tokio_sleep"), target_arch = "wasm32"))]
-mod thread;
+pub(crate) mod thread;
+mod timeout;
+
+pub use self::timeout::{Timeout, TimeoutExt};
 
 #[cfg(any(not(feature = "tokio_sleep"), target_arch = "wasm32"))]
 pub use self::thread::{sleep, Sleep};
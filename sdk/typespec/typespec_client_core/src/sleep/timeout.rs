@@ -4,17 +4,21 @@
 // Licensed under either of Apache License, Version 2.0 or MIT license at your option.
 
 // cspell:ignore Yoshua Wuyts
-use azure_core::sleep::{sleep, Sleep};
+use super::{sleep, Sleep};
 use futures::Future;
 use std::time::Duration;
 use std::{
     pin::Pin,
     task::{Context, Poll},
 };
+use typespec::error::{Error, ErrorKind};
 
+/// A future that completes with an [`ErrorKind::Other`] error if `future` does not complete before `deadline`.
+///
+/// Created by [`TimeoutExt::timeout`].
 #[pin_project::pin_project]
 #[derive(Debug)]
-pub(crate) struct Timeout<F, D> {
+pub struct Timeout<F, D> {
     #[pin]
     future: F,
     #[pin]
@@ -23,7 +27,8 @@ pub(crate) struct Timeout<F, D> {
 }
 
 impl<F, D> Timeout<F, D> {
-    pub(crate) fn new(future: F, deadline: D) -> Self {
+    /// Creates a `Timeout` that completes when either `future` or `deadline` completes.
+    pub fn new(future: F, deadline: D) -> Self {
         Self {
             future,
             deadline,
@@ -33,7 +38,7 @@ impl<F, D> Timeout<F, D> {
 }
 
 impl<F: Future, D: Future> Future for Timeout<F, D> {
-    type Output = azure_core::Result<F::Output>;
+    type Output = typespec::Result<F::Output>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
@@ -48,10 +53,7 @@ impl<F: Future, D: Future> Future for Timeout<F, D> {
             Poll::Pending => match this.deadline.poll(cx) {
                 Poll::Ready(_) => {
                     *this.completed = true;
-                    Poll::Ready(Err(azure_core::error::Error::with_message(
-                        azure_core::error::ErrorKind::Other,
-                        || String::from("operation timed out"),
-                    )))
+                    Poll::Ready(Err(Error::message(ErrorKind::Other, "operation timed out")))
                 }
                 Poll::Pending => Poll::Pending,
             },
@@ -59,7 +61,11 @@ impl<F: Future, D: Future> Future for Timeout<F, D> {
     }
 }
 
-pub(crate) trait TimeoutExt: Future {
+/// Extends a [`Future`] with a timeout.
+pub trait TimeoutExt: Future {
+    /// Requires the future to complete within the given `duration`.
+    ///
+    /// If the future does not complete in time, it is dropped and an [`ErrorKind::Other`] error is returned.
     fn timeout(self, duration: Duration) -> Timeout<Self, Sleep>
     where
         Self: Sized,
@@ -2,12 +2,16 @@
 // Licensed under the MIT License.
 
 mod bytes_stream;
+mod progress;
+mod throttle;
 
 use bytes::Bytes;
 pub use bytes_stream::*;
 use dyn_clone::DynClone;
 use futures::{io::AsyncRead, stream::Stream, task::Poll};
+pub use progress::*;
 use std::{pin::Pin, task::Context};
+pub use throttle::*;
 use typespec::error::{Error, ErrorKind, Result};
 
 /// Amount of the stream to buffer in memory during streaming uploads.
@@ -0,0 +1,158 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::SeekableStream;
+use futures::io::AsyncRead;
+use std::{
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// The direction of a transfer reported to a [`ProgressCallback`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferDirection {
+    /// A request body is being sent.
+    Upload,
+
+    /// A response body is being received.
+    Download,
+}
+
+/// The progress of an upload or download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferProgress {
+    /// Whether bytes are being sent or received.
+    pub direction: TransferDirection,
+
+    /// The number of bytes transferred so far.
+    ///
+    /// This is reported as 0 when an upload is reset to be retried.
+    pub bytes_transferred: u64,
+
+    /// The total number of bytes to transfer, if known.
+    pub total_bytes: Option<u64>,
+}
+
+/// A callback that reports the progress of uploads and downloads.
+///
+/// Add a `ProgressCallback` to the [`Context`](crate::http::Context) passed to a client method to report progress
+/// for [`Body::SeekableStream`](crate::http::Body::SeekableStream) uploads and response body downloads.
+/// The callback is called on each chunk, so it should return quickly.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(TransferProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Creates a `ProgressCallback` that calls `callback` with each update.
+    pub fn new(callback: impl Fn(TransferProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// Reports `progress` to the callback.
+    pub fn report(&self, progress: TransferProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// A [`SeekableStream`] that reports the bytes read from another stream to a [`ProgressCallback`].
+///
+/// Resetting the stream reports that no bytes have been transferred.
+#[derive(Clone, Debug)]
+pub struct ProgressStream {
+    inner: Box<dyn SeekableStream>,
+    callback: ProgressCallback,
+    bytes_read: u64,
+}
+
+impl ProgressStream {
+    /// Wraps `inner` to report the bytes read to `callback`.
+    pub fn new(inner: Box<dyn SeekableStream>, callback: ProgressCallback) -> Self {
+        Self {
+            inner,
+            callback,
+            bytes_read: 0,
+        }
+    }
+
+    fn report(&self) {
+        self.callback.report(TransferProgress {
+            direction: TransferDirection::Upload,
+            bytes_transferred: self.bytes_read,
+            total_bytes: Some(self.inner.len() as u64),
+        });
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl SeekableStream for ProgressStream {
+    async fn reset(&mut self) -> crate::Result<()> {
+        self.inner.reset().await?;
+        self.bytes_read = 0;
+        self.report();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn buffer_size(&self) -> usize {
+        self.inner.buffer_size()
+    }
+}
+
+impl AsyncRead for ProgressStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(bytes_read)) = result {
+            if bytes_read > 0 {
+                this.bytes_read += bytes_read as u64;
+                this.report();
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::BytesStream;
+    use futures::io::AsyncReadExt;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn reports_reads_and_resets() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let callback = {
+            let reported = reported.clone();
+            ProgressCallback::new(move |progress| {
+                reported.lock().unwrap().push(progress.bytes_transferred)
+            })
+        };
+        let mut stream = ProgressStream::new(Box::new(BytesStream::new("hello world")), callback);
+
+        let mut buf = [0; 6];
+        stream.read_exact(&mut buf).await.unwrap();
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).await.unwrap();
+        stream.reset().await.unwrap();
+        stream.read_to_end(&mut rest).await.unwrap();
+
+        assert_eq!(*reported.lock().unwrap(), vec![6, 11, 0, 11]);
+        assert_eq!(b"worldhello world", rest.as_slice());
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::SeekableStream;
+use crate::{
+    async_runtime::{AsyncRuntime, SleepFuture},
+    date::{self, Clock, OffsetDateTime, SystemClock},
+};
+#[cfg(feature = "http")]
+use bytes::Bytes;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::io::AsyncRead;
+use futures::ready;
+#[cfg(feature = "http")]
+use futures::Stream;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fmt;
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Limits the number of bytes per second transferred by the streams it is applied to.
+///
+/// Cloning a `BandwidthLimiter` shares the same limit, so streams of concurrent requests can share a single limit.
+/// Up to one second's worth of bytes may be transferred at once after a period of inactivity.
+///
+/// # Example
+///
+/// Limiting uploads to 1 MiB per second.
+/// ```
+/// # use typespec_client_core::{async_runtime::default_runtime, stream::{BandwidthLimiter, BytesStream, ThrottledStream}};
+/// let limiter = BandwidthLimiter::new(1024 * 1024);
+/// let stream = ThrottledStream::new(Box::new(BytesStream::new("hello")), limiter, default_runtime());
+/// ```
+#[derive(Clone, Debug)]
+pub struct BandwidthLimiter {
+    bytes_per_second: u64,
+    bucket: Arc<Mutex<Bucket>>,
+    clock: Arc<dyn Clock>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available bytes. Negative when bytes have been reserved by waiting streams.
+    tokens: f64,
+    last_refill: OffsetDateTime,
+}
+
+impl BandwidthLimiter {
+    /// Creates a `BandwidthLimiter` that allows `bytes_per_second` bytes per second.
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self::with_clock(bytes_per_second, Arc::new(SystemClock))
+    }
+
+    /// Creates a `BandwidthLimiter` that allows `bytes_per_second` bytes per second, measured using the `clock`.
+    pub fn with_clock(bytes_per_second: u64, clock: Arc<dyn Clock>) -> Self {
+        let bytes_per_second = bytes_per_second.max(1);
+        Self {
+            bytes_per_second,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: bytes_per_second as f64,
+                last_refill: clock.now(),
+            })),
+            clock,
+        }
+    }
+
+    /// Gets the number of bytes allowed per second.
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// The largest number of bytes to read at once.
+    fn max_chunk(&self) -> usize {
+        usize::try_from(self.bytes_per_second).unwrap_or(usize::MAX)
+    }
+
+    /// Reserves `bytes` and returns how long to wait before they may be transferred.
+    fn reserve(&self, bytes: usize) -> Duration {
+        let rate = self.bytes_per_second as f64;
+        let now = self.clock.now();
+        let mut bucket = self.bucket.lock().expect("lock poisoned");
+        if now > bucket.last_refill {
+            let elapsed = date::diff(now, bucket.last_refill);
+            bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(rate);
+            bucket.last_refill = now;
+        }
+        bucket.tokens -= bytes as f64;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+
+    /// Returns `bytes` that were reserved but not transferred.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn refund(&self, bytes: usize) {
+        if bytes > 0 {
+            let rate = self.bytes_per_second as f64;
+            let mut bucket = self.bucket.lock().expect("lock poisoned");
+            bucket.tokens = (bucket.tokens + bytes as f64).min(rate);
+        }
+    }
+}
+
+/// A pending delay that can be shared between threads while it is not polled.
+#[cfg(any(feature = "http", not(target_arch = "wasm32")))]
+struct Delay(Mutex<Option<SleepFuture>>);
+
+#[cfg(any(feature = "http", not(target_arch = "wasm32")))]
+impl Delay {
+    fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    fn start(&mut self, runtime: &dyn AsyncRuntime, duration: Duration) {
+        if !duration.is_zero() {
+            *self.0.get_mut().expect("lock poisoned") = Some(runtime.sleep(duration));
+        }
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let delay = self.0.get_mut().expect("lock poisoned");
+        if let Some(sleep) = delay {
+            ready!(sleep.as_mut().poll(cx));
+            *delay = None;
+        }
+        Poll::Ready(())
+    }
+}
+
+/// A [`SeekableStream`] that limits the bytes read from another stream using a [`BandwidthLimiter`].
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ThrottledStream {
+    inner: Box<dyn SeekableStream>,
+    limiter: BandwidthLimiter,
+    runtime: Arc<dyn AsyncRuntime>,
+    reserved: Option<usize>,
+    delay: Delay,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ThrottledStream {
+    /// Wraps `inner` to limit the bytes read using `limiter`, waiting on `runtime`.
+    pub fn new(
+        inner: Box<dyn SeekableStream>,
+        limiter: BandwidthLimiter,
+        runtime: Arc<dyn AsyncRuntime>,
+    ) -> Self {
+        Self {
+            inner,
+            limiter,
+            runtime,
+            reserved: None,
+            delay: Delay::new(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clone for ThrottledStream {
+    fn clone(&self) -> Self {
+        Self::new(
+            self.inner.clone(),
+            self.limiter.clone(),
+            self.runtime.clone(),
+        )
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Debug for ThrottledStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThrottledStream")
+            .field("inner", &self.inner)
+            .field("limiter", &self.limiter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait::async_trait]
+impl SeekableStream for ThrottledStream {
+    async fn reset(&mut self) -> crate::Result<()> {
+        if let Some(reserved) = self.reserved.take() {
+            self.limiter.refund(reserved);
+        }
+        self.delay = Delay::new();
+        self.inner.reset().await
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn buffer_size(&self) -> usize {
+        self.inner.buffer_size()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AsyncRead for ThrottledStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let reserved = match this.reserved {
+            Some(reserved) => reserved,
+            None => {
+                let reserved = buf.len().min(this.limiter.max_chunk());
+                let wait = this.limiter.reserve(reserved);
+                this.delay.start(this.runtime.as_ref(), wait);
+                this.reserved = Some(reserved);
+                reserved
+            }
+        };
+        ready!(this.delay.poll(cx));
+
+        let len = reserved.min(buf.len());
+        let result = ready!(Pin::new(&mut this.inner).poll_read(cx, &mut buf[..len]));
+        this.reserved = None;
+        let bytes_read = *result.as_ref().unwrap_or(&0);
+        this.limiter.refund(reserved - bytes_read);
+        Poll::Ready(result)
+    }
+}
+
+/// A stream of [`Bytes`] that delays each chunk until it is allowed by a [`BandwidthLimiter`].
+#[cfg(feature = "http")]
+pub(crate) struct Throttled<S> {
+    inner: S,
+    limiter: BandwidthLimiter,
+    runtime: Arc<dyn AsyncRuntime>,
+    pending: Option<Bytes>,
+    delay: Delay,
+}
+
+#[cfg(feature = "http")]
+impl<S> Throttled<S> {
+    pub(crate) fn new(inner: S, limiter: BandwidthLimiter, runtime: Arc<dyn AsyncRuntime>) -> Self {
+        Self {
+            inner,
+            limiter,
+            runtime,
+            pending: None,
+            delay: Delay::new(),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl<S> Stream for Throttled<S>
+where
+    S: Stream<Item = crate::Result<Bytes>> + Unpin,
+{
+    type Item = crate::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.pending.is_none() {
+            match ready!(Pin::new(&mut this.inner).poll_next(cx)) {
+                Some(Ok(bytes)) => {
+                    let wait = this.limiter.reserve(bytes.len());
+                    this.delay.start(this.runtime.as_ref(), wait);
+                    this.pending = Some(bytes);
+                }
+                other => return Poll::Ready(other),
+            }
+        }
+        ready!(this.delay.poll(cx));
+        Poll::Ready(this.pending.take().map(Ok))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{async_runtime::RecordingRuntime, date::ManualClock, stream::BytesStream};
+    use futures::io::AsyncReadExt;
+    #[cfg(feature = "http")]
+    use futures::StreamExt;
+
+    #[test]
+    fn reserves_bytes_per_second() {
+        let clock = ManualClock::default();
+        let limiter = BandwidthLimiter::with_clock(100, Arc::new(clock.clone()));
+        assert_eq!(Duration::ZERO, limiter.reserve(100));
+        assert_eq!(Duration::from_millis(500), limiter.reserve(50));
+
+        // Clones share the same limit.
+        let shared = limiter.clone();
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(Duration::from_millis(500), shared.reserve(100));
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(Duration::ZERO, shared.reserve(100));
+    }
+
+    #[tokio::test]
+    async fn throttles_stream_reads() {
+        let runtime = Arc::new(RecordingRuntime::default());
+        let limiter = BandwidthLimiter::new(4);
+        let mut stream = ThrottledStream::new(
+            Box::new(BytesStream::new("hello world")),
+            limiter,
+            runtime.clone(),
+        );
+
+        let mut body = Vec::new();
+        stream.read_to_end(&mut body).await.unwrap();
+        assert_eq!(b"hello world", body.as_slice());
+
+        // The first 4 bytes are sent at once and the rest wait for the limit.
+        let sleeps = runtime.sleeps();
+        assert_eq!(3, sleeps.len());
+        assert!(sleeps
+            .iter()
+            .all(|sleep| *sleep > Duration::from_millis(900)));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn throttles_stream_chunks() {
+        let runtime = Arc::new(RecordingRuntime::default());
+        let limiter = BandwidthLimiter::new(10);
+        let chunks = futures::stream::iter(["hello", " ", "world"].map(|s| Ok(Bytes::from(s))));
+        let throttled = Throttled::new(chunks, limiter, runtime.clone());
+
+        let body: Vec<Bytes> = throttled.map(Result::unwrap).collect().await;
+        assert_eq!(vec!["hello", " ", "world"], body);
+
+        let sleeps = runtime.sleeps();
+        assert_eq!(1, sleeps.len());
+        assert!(sleeps[0] > Duration::from_millis(50));
+    }
+}